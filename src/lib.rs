@@ -17,10 +17,11 @@
 //! total amount of contributions divided by the number of payout cycles
 //! and the number of members in the club.
 //!
-//! After the final payout cycle, any member can finalize the club
-//! and receive the remaining balance of the club's contributions.
-//! The smart contract transfers the balance to the account of the
-//! member who finalizes the club. This can be done only once.
+//! After the final payout cycle, any member can finalize the club.
+//! The remaining balance of the club is handled according to the end
+//! of life policy set when initializing the contract: it is shared
+//! equally among the members, sent to the creator, or locked in the
+//! contract for good. This can be done only once.
 //!
 //! Terminology: `Members` are the individuals who join the club
 //! by invoking the join function and sending the specified amount
@@ -30,7 +31,7 @@
 
 use concordium_std::{Duration, *};
 use core::fmt::Debug;
-use std::{collections::BTreeSet,};
+use std::collections::{BTreeMap, BTreeSet};
 //  ops::Add, time::Duration as STDDuration
 // use chrono::{DateTime, Duration, Utc};
 
@@ -41,13 +42,149 @@ pub enum TandaState {
     /// The Tanda has reached its maximum number of members and is no longer
     /// accepting new members.
     Closed,
-    /// The Tanda has not yet started the payout cycles.
+    /// The withdrawal phase has been configured and the Tanda is awaiting
+    /// its first withdrawal window. The first withdrawal moves it to
+    /// `InProgress`.
     Pending,
     /// The Tanda is in progress and is currently paying out to members.
     InProgress,
     /// The Tanda has completed all payout cycles and is ready for finalization.
     Completed,
 }
+/// Where the deposit of a member who forfeits goes.
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq)]
+pub enum ForfeitDestination {
+    /// The deposit is added to the pot.
+    Pot,
+    /// The deposit is shared equally among the remaining members.
+    Members,
+    /// The deposit is sent to the creator.
+    Creator,
+}
+/// What happens to the balance left in the contract when the club is finalized.
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq)]
+pub enum EndOfLifePolicy {
+    /// The balance is shared equally among the members.
+    DistributeEqually,
+    /// The balance is sent to the creator.
+    ToCreator,
+    /// The balance stays locked in the contract for good.
+    Burn,
+}
+/// Where the indivisible remainder of sharing the balance equally among
+/// the members goes when the club is finalized.
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq)]
+pub enum DustPolicy {
+    /// The remainder goes to the member who joined first.
+    Members,
+    /// The remainder is sent to the account finalizing the club.
+    Finalizer,
+}
+/// How contribution deadlines are scheduled.
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq)]
+pub enum DeadlineMode {
+    /// All members share the cycles counted from the start time.
+    Global,
+    /// Each member's cycles are counted from when they joined, or from the
+    /// start time if they joined before it.
+    RollingFromJoin,
+}
+/// Whether the club can be finalized, as returned by `canFinalize`.
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq)]
+pub enum FinalizeEligibility {
+    /// All payout cycles are complete and the club can be finalized.
+    Ok,
+    /// Payout cycles remain.
+    NotStarted,
+    /// The club is already finalized.
+    AlreadyFinalized,
+}
+/// An action an account can take on the club, as returned by
+/// `getAvailableActions`.
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq)]
+pub enum ActionKind {
+    /// Join the club with `joinTanda`.
+    Join,
+    /// Pay the current cycle with `contribute`.
+    Contribute,
+    /// Start the payout phase with `start_withdrawal_phase`.
+    StartPayouts,
+    /// Receive the payout of the current cycle with `withdraw`.
+    Withdraw,
+    /// Claim credited payouts and refunds with `claim`.
+    Claim,
+    /// Finalize the club with `finalize`.
+    Finalize,
+    /// Settle a club that timed out with `settleIncomplete`.
+    SettleIncomplete,
+}
+/// An optional setting of the club and its value, as returned by
+/// `getNonDefaultConfig`.
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq)]
+pub enum ConfigField {
+    /// `backup_finalizer_delay`
+    BackupFinalizerDelay(Duration),
+    /// `late_join_multiplier`
+    LateJoinMultiplier(u64),
+    /// `forfeit_destination`
+    ForfeitDestination(ForfeitDestination),
+    /// `decimals`
+    Decimals(u8),
+    /// `allow_prepay`
+    AllowPrepay(bool),
+    /// `min_members`
+    MinMembers(u64),
+    /// `end_of_life`
+    EndOfLife(EndOfLifePolicy),
+    /// `reminder_window`
+    ReminderWindow(Duration),
+    /// `cosign_threshold`
+    CosignThreshold(Option<Amount>),
+    /// `join_fee`
+    JoinFee(Amount),
+    /// `sub_round_size`
+    SubRoundSize(Option<u64>),
+    /// `abort_supermajority_bps`
+    AbortSupermajorityBps(u64),
+    /// `max_payout_per_cycle`
+    MaxPayoutPerCycle(Option<Amount>),
+    /// `deadline_mode`
+    DeadlineMode(DeadlineMode),
+    /// `min_streak`
+    MinStreak(u64),
+    /// `payout_quorum_bps`
+    PayoutQuorumBps(u64),
+    /// `probation_cycles`
+    ProbationCycles(u64),
+    /// `escalation_bps`
+    EscalationBps(u16),
+    /// `lock_terms`
+    LockTerms(bool),
+    /// `final_cycle_tolerance`
+    FinalCycleTolerance(Amount),
+    /// `dust_policy`
+    DustPolicy(DustPolicy),
+    /// `auto_penalize`
+    AutoPenalize(bool),
+    /// `abort_vote_delay`
+    AbortVoteDelay(Duration),
+    /// `strict_finalize`
+    StrictFinalize(bool),
+    /// `min_join_gap`
+    MinJoinGap(Duration),
+    /// `early_bird_count`
+    EarlyBirdCount(u64),
+    /// `early_bird_penalty`
+    EarlyBirdPenalty(Amount),
+    /// `collateral_amount`
+    CollateralAmount(Amount),
+    /// `min_pot_for_payout`
+    MinPotForPayout(Amount),
+    /// `soft_close_bps`
+    SoftCloseBps(Option<u16>),
+    /// `contribution_cooldown_divisor`
+    ContributionCooldownDivisor(u64),
+}
 /// Your smart contract state.
 // pub struct State {
 //     // Your state
@@ -111,6 +248,773 @@ pub struct State {
     max_contributors: u64,
     /// Index of users of members, just used to increment the member attribute index
     user_index: u64,
+    /// The last time each member made a contribution
+    last_contribution_times: BTreeMap<AccountAddress, Timestamp>,
+    /// Whether the Tanda club has been finalized
+    finalized: bool,
+    /// An account allowed to finalize the club without being a member
+    backup_finalizer: Option<AccountAddress>,
+    /// How long after the end time the backup finalizer has to wait
+    backup_finalizer_delay: Duration,
+    /// The factor applied to the penalty deposit of members joining late
+    late_join_multiplier: u64,
+    /// The penalty deposit each member paid when joining
+    deposits: BTreeMap<AccountAddress, Amount>,
+    /// Where the deposit of a member who forfeits goes
+    forfeit_destination: ForfeitDestination,
+    /// The amount paid out by the most recent withdrawal
+    last_payout_amount: Amount,
+    /// The number of decimals used to display amounts, not used in any computation
+    decimals: u8,
+    /// Whether members can prepay future cycles by sending a multiple of the contribution amount
+    allow_prepay: bool,
+    /// The contribution cycles each member has paid for, including prepaid ones
+    contributed_cycles: BTreeMap<AccountAddress, BTreeSet<u64>>,
+    /// The minimum number of members required before joining can be closed early
+    min_members: u64,
+    /// What happens to the balance left when the club is finalized
+    end_of_life: EndOfLifePolicy,
+    /// Payouts credited to members that they have not claimed yet
+    withdrawable_balances: BTreeMap<AccountAddress, Amount>,
+    /// Members skipped because their account no longer exists, or because
+    /// they had not contributed when the payout phase started
+    skipped_receivers: Vec<AccountAddress>,
+    /// How long before a contribution deadline reminders can be emitted
+    reminder_window: Duration,
+    /// The last contribution cycle a reminder was emitted for
+    last_reminder_cycle: Option<u64>,
+    /// Payouts above this amount need the approval of the creator
+    cosign_threshold: Option<Amount>,
+    /// The member whose withdrawal awaits the approval of the creator
+    pending_withdrawal: Option<AccountAddress>,
+    /// Opaque metadata attached by members for off-chain notifiers
+    member_metadata: BTreeMap<AccountAddress, Vec<u8>>,
+    /// The non-refundable fee paid into the pot when joining
+    join_fee: Amount,
+    /// The number of members paid out per sub-round, if the rotation is
+    /// split into sub-rounds
+    sub_round_size: Option<u64>,
+    /// The sub-round currently being paid out
+    sub_round: u64,
+    /// The time when the club was created
+    created_at: Timestamp,
+    /// Whether all deposits have been refunded by `finalizeRefundBatch`
+    refunds_complete: bool,
+    /// The share of members, in basis points, needed to abort the club
+    abort_supermajority_bps: u64,
+    /// The accounts that voted to abort the club
+    abort_votes: BTreeSet<AccountAddress>,
+    /// Whether the club was aborted by a vote of its members
+    aborted: bool,
+    /// The maximum amount paid out per cycle, if capped
+    max_payout_per_cycle: Option<Amount>,
+    /// The total amount paid out of the pot so far
+    total_paid_out: Amount,
+    /// How contribution deadlines are scheduled
+    deadline_mode: DeadlineMode,
+    /// The time each member joined at
+    joined_at: BTreeMap<AccountAddress, Timestamp>,
+    /// The number of times each member deferred their payout
+    deferrals: BTreeMap<AccountAddress, u64>,
+    /// The contribution streak needed to be paid out before members without it
+    min_streak: u64,
+    /// The contributions of each member, without penalties
+    member_contributions: BTreeMap<AccountAddress, Amount>,
+    /// The late penalties paid by each member
+    penalties_paid: BTreeMap<AccountAddress, Amount>,
+    /// The payouts credited to each member
+    payouts_received: BTreeMap<AccountAddress, Amount>,
+    /// Every payout made, in order
+    payout_history: Vec<PayoutRecord>,
+    /// The share of members, in basis points, who must have contributed
+    /// to start the payout phase
+    payout_quorum_bps: u64,
+    /// The number of cycles after joining during which a member is paid
+    /// out only after the others
+    probation_cycles: u64,
+    /// The accounts waiting for a slot in a full club, with the amount
+    /// they paid to join, in order of arrival
+    waitlist: Vec<(AccountAddress, Amount)>,
+    /// The increase of the late penalty per earlier default, in basis points
+    escalation_bps: u16,
+    /// The number of late contributions of each member
+    defaults: BTreeMap<AccountAddress, u64>,
+    /// The rotation order frozen when the payout phase started
+    frozen_order: Vec<AccountAddress>,
+    /// The number of members that joined, including from the waitlist
+    join_count: u64,
+    /// The number of contributions made
+    contribution_count: u64,
+    /// The number of payouts made
+    payout_count: u64,
+    /// The total of the late penalties collected
+    penalties_collected: Amount,
+    /// Whether the club was settled after its end time passed before all
+    /// payout cycles were completed
+    settled: bool,
+    /// Whether the terms are locked on the first contribution
+    lock_terms: bool,
+    /// Whether the terms can no longer be changed
+    terms_locked: bool,
+    /// How much a contribution for the final cycle may fall short
+    final_cycle_tolerance: Amount,
+    /// Where the remainder of sharing the balance equally goes
+    dust_policy: DustPolicy,
+    /// Whether missed cycles are penalized automatically from the deposit
+    auto_penalize: bool,
+    /// The members left out of the rotation for not contributing, who can
+    /// still catch up
+    carried_over: Vec<AccountAddress>,
+    /// How long after the start time voting to abort opens
+    abort_vote_delay: Duration,
+    /// The number of contribution cycles already checked for defaults,
+    /// per member
+    swept_cycles: BTreeMap<AccountAddress, u64>,
+    /// Whether finalizing also waits for the end time
+    strict_finalize: bool,
+    /// Shares of the remaining balance that could not be transferred
+    /// because the member's account was missing
+    failed_payouts: BTreeMap<AccountAddress, Amount>,
+    /// The number of failed retries of a failed payout, per member
+    payout_retries: BTreeMap<AccountAddress, u64>,
+    /// The minimum time between two joins
+    min_join_gap: Duration,
+    /// When the last member joined
+    last_join_time: Option<Timestamp>,
+    /// The number of first joiners who pay the early bird penalty deposit
+    early_bird_count: u64,
+    /// The reduced penalty deposit of the early birds
+    early_bird_penalty: Amount,
+    /// The collateral every member locks when joining
+    collateral_amount: Amount,
+    /// The collateral locked by each member, not returned yet
+    collaterals: BTreeMap<AccountAddress, Amount>,
+    /// The smallest pot that can be paid out
+    min_pot_for_payout: Amount,
+    /// When each entrypoint was last called successfully, by name
+    activity: BTreeMap<String, Timestamp>,
+    /// The share of the member slots, in basis points, at which joining
+    /// closes automatically
+    soft_close_bps: Option<u16>,
+    /// The payment interval divided by this value is the cooldown between
+    /// two contributions of the same member
+    contribution_cooldown_divisor: u64,
+}
+
+impl State {
+    /// Whether the address has joined the Tanda club.
+    fn is_member(&self, address: &AccountAddress) -> bool {
+        self.members
+            .as_ref()
+            .map_or(false, |members| members.iter().any(|(addr, _)| addr == address))
+    }
+
+    /// The amount an account joining at `now` has to pay: the penalty
+    /// deposit plus the join fee and the collateral.
+    fn required_join_amount(&self, now: Timestamp) -> Result<Amount, Error> {
+        self.required_deposit(now)?
+            .micro_ccd
+            .checked_add(self.join_fee.micro_ccd)
+            .and_then(|amount| amount.checked_add(self.collateral_amount.micro_ccd))
+            .map(Amount::from_micro_ccd)
+            .ok_or(Error::InvalidState)
+    }
+
+    /// The penalty deposit an account joining at `now` has to pay. The
+    /// first `early_bird_count` joiners pay the early bird penalty instead
+    /// of the penalty amount. Accounts joining after the first payment
+    /// interval has passed pay the deposit multiplied by the late join
+    /// multiplier.
+    fn required_deposit(&self, now: Timestamp) -> Result<Amount, Error> {
+        let penalty = if self.join_count < self.early_bird_count {
+            self.early_bird_penalty
+        } else {
+            self.penalty_amount
+        };
+        let late_join_time = self
+            .start_time
+            .checked_add(self.time_interval)
+            .ok_or(Error::InvalidState)?;
+        if now < late_join_time {
+            return Ok(penalty);
+        }
+        penalty
+            .micro_ccd
+            .checked_mul(self.late_join_multiplier.max(1))
+            .map(Amount::from_micro_ccd)
+            .ok_or(Error::InvalidState)
+    }
+
+    /// The contribution cycle at `now`: the number of full payment
+    /// intervals that have elapsed since the start time.
+    fn contribution_cycle(&self, now: Timestamp) -> u64 {
+        self.cycles_since(self.start_time, now)
+    }
+
+    /// The contribution cycle of the member at `now`, which depends on the
+    /// deadline mode.
+    fn member_cycle(&self, member: &AccountAddress, now: Timestamp) -> u64 {
+        self.cycles_since(self.cycle_anchor(member), now)
+    }
+
+    /// The time from which the contribution cycles of the member are
+    /// counted.
+    fn cycle_anchor(&self, member: &AccountAddress) -> Timestamp {
+        match self.deadline_mode {
+            DeadlineMode::Global => self.start_time,
+            DeadlineMode::RollingFromJoin => self
+                .joined_at
+                .get(member)
+                .map_or(self.start_time, |joined_at| (*joined_at).max(self.start_time)),
+        }
+    }
+
+    /// The number of full payment intervals between `anchor` and `now`.
+    fn cycles_since(&self, anchor: Timestamp, now: Timestamp) -> u64 {
+        now.duration_since(anchor)
+            .and_then(|elapsed| elapsed.millis().checked_div(self.time_interval.millis()))
+            .unwrap_or(0)
+    }
+
+    /// The amount paid out for the current cycle: an equal share of the
+    /// part of the pot not paid out yet over the payouts still to be made,
+    /// capped at the maximum payout per cycle. The excess rolls into later
+    /// cycles, and the last payout takes what is left.
+    fn current_payout(&self) -> Amount {
+        let pot = self.distributable_pot();
+        // With no payouts left to split over, whatever is left is paid out.
+        let share = per_cycle_share(pot, self.remaining_payouts()).unwrap_or(pot);
+        match self.max_payout_per_cycle {
+            Some(cap) if cap < share => cap,
+            _ => share,
+        }
+    }
+
+    /// The number of payouts still to be made, including the current one:
+    /// one for every remaining cycle, but no more than there are members
+    /// waiting for their payout.
+    fn remaining_payouts(&self) -> u64 {
+        let cycles = self.payout_cycle.saturating_sub(self.current_cycle);
+        let pending = self
+            .members
+            .iter()
+            .flatten()
+            .filter(|(address, _)| is_pending_receiver(self, address))
+            .count() as u64;
+        cycles.min(pending)
+    }
+
+    /// Whether the payout for the current cycle needs the approval of the creator.
+    fn needs_cosign(&self) -> bool {
+        self.cosign_threshold
+            .map_or(false, |threshold| self.current_payout() > threshold)
+    }
+
+    /// The number of votes of current members needed to abort the club.
+    fn abort_vote_threshold(&self) -> u64 {
+        let members = self.members.as_ref().map_or(0, |v| v.len()) as u64;
+        let needed = (members as u128 * self.abort_supermajority_bps as u128
+            + BPS_DENOMINATOR as u128
+            - 1)
+            / BPS_DENOMINATOR as u128;
+        (needed as u64).max(1)
+    }
+
+    /// The part of the pot that has not been paid out yet.
+    fn distributable_pot(&self) -> Amount {
+        Amount::from_micro_ccd(
+            self.total_contributions
+                .micro_ccd
+                .saturating_sub(self.total_paid_out.micro_ccd),
+        )
+    }
+
+    /// The number of current members who voted to abort the club.
+    fn abort_vote_count(&self) -> u64 {
+        self.abort_votes
+            .iter()
+            .filter(|voter| self.is_member(voter))
+            .count() as u64
+    }
+
+    /// The total of the penalty deposits and collateral not refunded yet,
+    /// including the amounts paid by waitlisted accounts.
+    fn deposits_total(&self) -> Amount {
+        let waitlisted = self
+            .waitlist
+            .iter()
+            .fold(Amount::zero(), |total, (_, amount)| total + *amount);
+        self.deposits
+            .values()
+            .chain(self.collaterals.values())
+            .fold(waitlisted, |total, deposit| total + *deposit)
+    }
+
+    /// The total of the payouts credited to members but not claimed yet,
+    /// including the failed payouts that can still be retried.
+    fn unclaimed_total(&self) -> Amount {
+        self.withdrawable_balances
+            .values()
+            .chain(self.failed_payouts.values())
+            .fold(Amount::zero(), |total, amount| total + *amount)
+    }
+
+    /// The end of the given contribution cycle, which is the deadline to
+    /// pay for it.
+    fn cycle_deadline(&self, cycle: u64) -> Option<Timestamp> {
+        self.deadline_after(self.start_time, cycle)
+    }
+
+    /// The deadline to pay for the given contribution cycle of the member,
+    /// which depends on the deadline mode.
+    fn member_cycle_deadline(&self, member: &AccountAddress, cycle: u64) -> Option<Timestamp> {
+        self.deadline_after(self.cycle_anchor(member), cycle)
+    }
+
+    /// The end of the given cycle counted from `anchor`.
+    fn deadline_after(&self, anchor: Timestamp, cycle: u64) -> Option<Timestamp> {
+        let offset = self.time_interval.millis().checked_mul(cycle.checked_add(1)?)?;
+        anchor.checked_add(Duration::from_millis(offset))
+    }
+
+    /// Whether the member has paid for the given contribution cycle.
+    fn has_contributed(&self, member: &AccountAddress, cycle: u64) -> bool {
+        self.contributed_cycles
+            .get(member)
+            .map_or(false, |cycles| cycles.contains(&cycle))
+    }
+
+    /// The number of consecutive cycles the member paid for, counting back
+    /// from the last cycle they paid for.
+    fn contribution_streak(&self, member: &AccountAddress) -> u64 {
+        let cycles = match self.contributed_cycles.get(member) {
+            Some(cycles) => cycles,
+            None => return 0,
+        };
+        let mut streak = 0;
+        let mut expected = None;
+        for cycle in cycles.iter().rev() {
+            if expected.map_or(false, |expected| expected != *cycle) {
+                break;
+            }
+            streak += 1;
+            expected = cycle.checked_sub(1);
+            if expected.is_none() {
+                break;
+            }
+        }
+        streak
+    }
+
+    /// Whether the member is still on probation for the current payout
+    /// cycle: fewer than `probation_cycles` cycles have passed since the
+    /// cycle they joined in. Cycles are counted through the contribution
+    /// phase and then the payout cycles.
+    fn in_probation(&self, member: &AccountAddress) -> bool {
+        let joined_at = match self.joined_at.get(member) {
+            Some(joined_at) => *joined_at,
+            None => return false,
+        };
+        let probation_end = self
+            .contribution_cycle(joined_at)
+            .saturating_add(self.probation_cycles);
+        self.payout_cycle.saturating_add(self.current_cycle) < probation_end
+    }
+
+    /// The penalty the member pays on top of a late contribution: the
+    /// penalty amount, increased by the escalation for every earlier
+    /// default of the member. Zero when missed cycles are penalized from
+    /// the deposit instead.
+    fn late_penalty(&self, member: &AccountAddress) -> Result<Amount, Error> {
+        if self.auto_penalize {
+            return Ok(Amount::zero());
+        }
+        self.escalated_penalty(self.defaults.get(member).copied().unwrap_or(0))
+    }
+
+    /// The late penalty of a member with the given number of earlier
+    /// defaults.
+    fn escalated_penalty(&self, defaults: u64) -> Result<Amount, Error> {
+        let penalty = self.penalty_amount.micro_ccd as u128;
+        let escalation =
+            penalty * self.escalation_bps as u128 * defaults as u128 / BPS_DENOMINATOR as u128;
+        let total = penalty + escalation;
+        ensure!(total <= u64::MAX as u128, Error::InvalidState);
+        Ok(Amount::from_micro_ccd(total as u64))
+    }
+
+    /// Whether a payment by the member for the given cycle is late, that is
+    /// the member did not pay for the previous cycle.
+    fn is_late(&self, member: &AccountAddress, cycle: u64) -> bool {
+        cycle > 0 && !self.has_contributed(member, cycle - 1)
+    }
+
+    /// Whether enough members have contributed to start the payout phase.
+    /// The quorum is a share of the members who joined, as joining can be
+    /// closed before the club is full.
+    fn has_payout_quorum(&self) -> bool {
+        let members = self.members.as_ref().map_or(0, |v| v.len()) as u128;
+        let contributors = self
+            .contributors
+            .iter()
+            .filter(|contributor| self.is_member(contributor))
+            .count() as u128;
+        let required = members * self.payout_quorum_bps as u128;
+        members > 0 && contributors * (BPS_DENOMINATOR as u128) >= required
+    }
+
+    /// Whether the member list is usable: it is set, its join indices are
+    /// unique and it includes every contributor. Deferrals in earlier
+    /// versions moved a member to the back of the rotation without
+    /// reordering the list, so the order alone does not make it unusable.
+    fn members_consistent(&self) -> bool {
+        let mut members = match self.members.clone() {
+            Some(members) => members,
+            None => return false,
+        };
+        members.sort_by_key(|(_, index)| *index);
+        self.is_consistent_roster(&members)
+    }
+
+    /// Whether the given roster is ordered by join index and includes every
+    /// contributor.
+    fn is_consistent_roster(&self, members: &[(AccountAddress, u64)]) -> bool {
+        validate_member_indices(members)
+            && self.contributors.iter().all(|contributor| {
+                members.iter().any(|(address, _)| address == contributor)
+            })
+    }
+
+    /// Whether enough members joined to close joining automatically: the
+    /// soft close share of the slots is taken and the minimum number of
+    /// members has joined.
+    fn reached_soft_close(&self) -> bool {
+        let soft_close_bps = match self.soft_close_bps {
+            Some(soft_close_bps) => soft_close_bps,
+            None => return false,
+        };
+        let members = self.members.as_ref().map_or(0, |v| v.len()) as u128;
+        members >= self.min_members as u128
+            && members * BPS_DENOMINATOR as u128
+                >= self.max_contributors as u128 * soft_close_bps as u128
+    }
+
+    /// Whether the club takes contributions at the given time: it is in its
+    /// contribution phase, within its time window, and has cycles left.
+    fn accepts_contributions(&self, now: Timestamp) -> bool {
+        (self.tanda_state == TandaState::Open || self.tanda_state == TandaState::Closed)
+            && now >= self.start_time
+            && now <= self.end_time
+            && self.contribution_cycle(now) < self.payout_cycle
+    }
+
+    /// The sub-round of the member with the given user index. Members are
+    /// grouped into sub-rounds in join order; without sub-rounds every
+    /// member is in sub-round 0.
+    fn sub_round_of(&self, index: u64) -> u64 {
+        match self.sub_round_size {
+            Some(size) if size > 0 => index.saturating_sub(1) / size,
+            _ => 0,
+        }
+    }
+
+    /// Whether the payout of the given cycle has been made.
+    fn is_cycle_paid(&self, cycle: u64) -> bool {
+        self.completed_cycles.iter().any(|(paid_cycle, _)| *paid_cycle == cycle)
+    }
+
+    /// When the next receiver can be paid out, as scheduled in
+    /// `next_withdrawal_time` when the payout phase opens and after each
+    /// payout.
+    fn next_payout_time(&self) -> Result<Timestamp, Error> {
+        ensure!(
+            self.withdrawal_phase_started && self.next_receiver.is_some(),
+            Error::InvalidState
+        );
+        Ok(self.next_withdrawal_time)
+    }
+
+    /// Whether all payout cycles are complete at `now`, counting the last
+    /// cycle as complete once it could be advanced past.
+    fn cycles_complete(&self, now: Timestamp) -> bool {
+        if self.current_cycle >= self.payout_cycle {
+            return true;
+        }
+        if self.is_stalled() {
+            return now
+                .duration_since(self.last_withdrawal_time)
+                .map_or(false, |dur| dur >= self.time_interval);
+        }
+        self.current_cycle + 1 == self.payout_cycle
+            && self.tanda_state != TandaState::Completed
+            && self.is_cycle_paid(self.current_cycle)
+            && now
+                .duration_since(self.last_withdrawal_time)
+                .map_or(false, |dur| dur >= self.time_interval)
+    }
+
+    /// Whether the payouts have started but no member is left to receive
+    /// one, for instance after forfeits or when joining closed early. The
+    /// cycles left can then never be paid out.
+    fn is_stalled(&self) -> bool {
+        self.withdrawal_phase_started
+            && self.remaining_payouts() == 0
+            && self.tanda_state != TandaState::Completed
+    }
+
+    /// Whether the club may be finalized at `now` as far as time goes.
+    /// Under strict finalization the end time has to be reached as well.
+    fn end_time_allows_finalize(&self, now: Timestamp) -> bool {
+        !self.strict_finalize || now >= self.end_time
+    }
+}
+
+/// The amount a member is expected to contribute for the given cycle.
+/// Every cycle currently has the same fixed contribution amount; this is
+/// the single place to change when the amount starts to depend on the cycle.
+fn expected_contribution(state: &State, _cycle: u64) -> Amount {
+    state.contribution_amount
+}
+
+/// The full rotation: members who already received a payout, in payout
+/// order, followed by the remaining members in the order in which
+/// `select_next_receiver` will select them.
+fn rotation_order(state: &State) -> Vec<AccountAddress> {
+    let mut order: Vec<AccountAddress> = state
+        .completed_cycles
+        .iter()
+        .flat_map(|(_, receivers)| receivers.iter().copied())
+        .collect();
+    if !state.frozen_order.is_empty() {
+        order.extend(
+            state
+                .frozen_order
+                .iter()
+                .filter(|address| is_pending_receiver(state, address))
+                .copied(),
+        );
+        return order;
+    }
+    let mut remaining: Vec<&(AccountAddress, u64)> = state
+        .members
+        .iter()
+        .flatten()
+        .filter(|(address, _)| !state.withdrawn_addresses.contains(address))
+        .filter(|(address, _)| !state.skipped_receivers.contains(address))
+        .collect();
+    remaining.sort_by_key(|(address, index)| receiver_priority(state, address, *index));
+    order.extend(remaining.into_iter().map(|(address, _)| *address));
+    order
+}
+
+/// Whether the account is a member still waiting for a payout.
+fn is_pending_receiver(state: &State, address: &AccountAddress) -> bool {
+    state.is_member(address)
+        && !state.withdrawn_addresses.contains(address)
+        && !state.skipped_receivers.contains(address)
+}
+
+/// Advances the club to the next cycle once the current cycle has been
+/// paid out and the payment interval has elapsed since that payout.
+/// The club is completed when it advances past its last payout cycle, or
+/// right away when no member is left to pay out.
+/// The state is only changed when the cycle is advanced.
+fn advance_cycle(state: &mut State, now: Timestamp) -> Result<(), Error> {
+    ensure!(
+        state.tanda_state != TandaState::Completed,
+        Error::AlreadyFinished
+    );
+    let stalled = state.is_stalled();
+    ensure!(
+        stalled || state.is_cycle_paid(state.current_cycle),
+        Error::InvalidState
+    );
+    ensure!(
+        now.duration_since(state.last_withdrawal_time)
+            .map_or(false, |dur| dur >= state.time_interval),
+        Error::WithdrawalIntervalNotReached
+    );
+
+    // Skip the cycles nobody is left to receive.
+    if stalled {
+        set_tanda_state(state, TandaState::Completed)?;
+        state.current_cycle = state.payout_cycle;
+        return Ok(());
+    }
+
+    let next_cycle = state.current_cycle + 1;
+    if next_cycle >= state.payout_cycle {
+        set_tanda_state(state, TandaState::Completed)?;
+    }
+    state.current_cycle = next_cycle;
+    Ok(())
+}
+
+/// Records that the entrypoint with the given name was called at `now`.
+fn record_activity(state: &mut State, name: &str, now: Timestamp) {
+    match state.activity.get_mut(name) {
+        Some(last_called) => *last_called = now,
+        None => {
+            state.activity.insert(String::from(name), now);
+        }
+    }
+}
+
+/// Checks that the club may move from one state to another. The club
+/// moves forward only: from `Open` to `Closed` when joining closes, to
+/// `Pending` when the payout phase starts, to `InProgress` on the first
+/// payout, and to `Completed` at the end. Aborting or settling completes
+/// the club from any earlier state. Staying in the same state is allowed.
+fn transition(from: TandaState, to: TandaState) -> Result<(), Error> {
+    use TandaState::*;
+    let allowed = from == to
+        || matches!(
+            (from, to),
+            (Open, Closed)
+                | (Open, Pending)
+                | (Closed, Pending)
+                | (Pending, InProgress)
+                | (Open, Completed)
+                | (Closed, Completed)
+                | (Pending, Completed)
+                | (InProgress, Completed)
+        );
+    ensure!(allowed, Error::InvalidState);
+    Ok(())
+}
+
+/// Moves the club to the given state if the transition is allowed.
+fn set_tanda_state(state: &mut State, to: TandaState) -> Result<(), Error> {
+    transition(state.tanda_state, to)?;
+    state.tanda_state = to;
+    Ok(())
+}
+
+/// Penalizes every member once for each ended contribution cycle they did
+/// not pay for, drawing the penalty amount from their deposit into the
+/// pot. Does nothing unless the club penalizes automatically.
+fn sweep_defaults(state: &mut State, now: Timestamp) {
+    if !state.auto_penalize {
+        return;
+    }
+    let members: Vec<AccountAddress> =
+        state.members.iter().flatten().map(|(address, _)| *address).collect();
+    for member in members {
+        let ended = state.member_cycle(&member, now).min(state.payout_cycle);
+        let first = state.swept_cycles.get(&member).copied().unwrap_or(0);
+        if ended <= first {
+            continue;
+        }
+        let missed = (first..ended)
+            .filter(|cycle| !state.has_contributed(&member, *cycle))
+            .count() as u64;
+        state.swept_cycles.insert(member, ended);
+
+        let penalty = state.penalty_amount.micro_ccd.saturating_mul(missed);
+        if let Some(deposit) = state.deposits.get_mut(&member) {
+            let slashed = Amount::from_micro_ccd(penalty.min(deposit.micro_ccd));
+            *deposit -= slashed;
+            state.total_contributions += slashed;
+        }
+    }
+}
+
+/// Selects the next member in line to receive a payout: the earliest
+/// joined member who has not received a payout yet. Members whose account
+/// no longer exists are skipped. With sub-rounds, only members of the
+/// current sub-round are selected. Members on probation or without the
+/// minimum contribution streak are only selected after the others.
+/// Once the payout phase has started, the order frozen at its start is
+/// followed instead.
+fn select_next_receiver(state: &State) -> Option<AccountAddress> {
+    if !state.frozen_order.is_empty() {
+        return state
+            .frozen_order
+            .iter()
+            .find(|address| is_pending_receiver(state, address))
+            .copied();
+    }
+    state
+        .members
+        .iter()
+        .flatten()
+        .filter(|(address, _)| !state.withdrawn_addresses.contains(address))
+        .filter(|(address, _)| !state.skipped_receivers.contains(address))
+        .filter(|(_, index)| state.sub_round_of(*index) == state.sub_round)
+        .min_by_key(|(address, index)| receiver_priority(state, address, *index))
+        .map(|(address, _)| *address)
+}
+
+/// The key the rotation is ordered by: members on probation come after
+/// the others, as do members whose contribution streak is below the
+/// minimum, then join order.
+fn receiver_priority(state: &State, member: &AccountAddress, index: u64) -> (bool, bool, u64) {
+    (
+        state.in_probation(member),
+        state.contribution_streak(member) < state.min_streak,
+        index,
+    )
+}
+
+/// Renumbers the members in join order after removals, so that their user
+/// indexes are again `1..=n`. The relative order, and so the rotation, is
+/// kept; the sub-round and next receiver are selected again because they
+/// depend on the indexes.
+fn compact_members(state: &mut State) {
+    let mut members = match state.members.take() {
+        Some(members) => members,
+        None => return,
+    };
+    members.sort_by_key(|(_, index)| *index);
+    for (position, member) in members.iter_mut().enumerate() {
+        member.1 = position as u64 + 1;
+    }
+    state.user_index = members.len() as u64;
+    state.members = Some(members);
+
+    if let Some(sub_round) = first_open_sub_round(state) {
+        state.sub_round = sub_round;
+    }
+    if state.withdrawal_phase_started {
+        state.next_receiver = select_next_receiver(state);
+    }
+}
+
+/// Selects the next receiver, moving on to the next sub-round once the
+/// current one is paid out.
+fn reselect_receiver(state: &mut State) {
+    state.next_receiver = select_next_receiver(state);
+    if state.next_receiver.is_none() {
+        if let Some(sub_round) = first_open_sub_round(state) {
+            state.sub_round = sub_round;
+            state.next_receiver = select_next_receiver(state);
+        }
+    }
+
+    // The frozen order moves through the sub-rounds by itself.
+    if let Some(receiver) = state.next_receiver {
+        let index = state
+            .members
+            .iter()
+            .flatten()
+            .find(|(address, _)| *address == receiver)
+            .map(|(_, index)| *index);
+        if let Some(index) = index {
+            state.sub_round = state.sub_round_of(index);
+        }
+    }
+}
+
+/// The earliest sub-round with members who still have to receive a payout.
+fn first_open_sub_round(state: &State) -> Option<u64> {
+    state
+        .members
+        .iter()
+        .flatten()
+        .filter(|(address, _)| !state.withdrawn_addresses.contains(address))
+        .filter(|(address, _)| !state.skipped_receivers.contains(address))
+        .map(|(_, index)| state.sub_round_of(*index))
+        .min()
 }
 /// Your smart contract errors.
 #[derive(Debug, PartialEq, Eq, Reject, Serial, SchemaType)]
@@ -188,8 +1092,83 @@ enum Error {
     InvalidParameter,
     /// An internal error occurred.
     InternalError,
+    /// Raised when a member contributes again before the cooldown has elapsed.
+    ContributionCooldown,
+    /// Raised when the backup finalizer acts before its delay has passed.
+    FinalizationDelayNotReached,
+    /// Raised when joining is closed before the minimum number of members joined.
+    MinimumNotReached,
+    /// Raised when the account has no credited payout to claim.
+    NothingToClaim,
+    /// Raised when a reminder is requested outside the reminder window.
+    OutsideReminderWindow,
+    /// Failed logging an event.
+    #[from(LogError)]
+    LogError,
+    /// Raised when a payout exceeds the co-sign threshold and needs approval.
+    CosignRequired,
+    /// Raised when there is no withdrawal request to approve.
+    NoPendingWithdrawal,
+    /// Raised when member metadata exceeds the maximum length.
+    MetadataTooLong,
+    /// Raised when the join fee exceeds the contribution amount.
+    InvalidJoinFee,
+    /// Raised when the sub-round size is zero.
+    InvalidSubRoundSize,
+    /// Raised when finalizing before all deposits have been refunded.
+    RefundsPending,
+    /// Raised when the abort supermajority is not between 1 and 10000 basis points.
+    InvalidAbortThreshold,
+    /// Raised when a member votes to abort more than once.
+    AlreadyVoted,
+    /// Raised when a member has deferred their payout too many times.
+    DeferralLimitReached,
+    /// Raised when the payout quorum is not between 1 and 10000 basis points.
+    InvalidPayoutQuorum,
+    /// Raised when a query asks for more than `MAX_PAGE_SIZE` entries.
+    TooManyEntries,
+    /// Raised when joining the waitlist of a club that still has free slots.
+    ClubNotFull,
+    /// Raised when the account is not on the waitlist.
+    NotWaitlisted,
+    /// Raised when the end time of the club has not passed yet.
+    EndTimeNotReached,
+    /// Raised when the member's slot was not carried over.
+    NotCarriedOver,
+    /// Raised when joining before the minimum gap since the last join has passed.
+    JoinTooSoon,
+    /// Raised when the soft close share is not between 1 and 10000 basis points.
+    InvalidSoftClose,
+    /// Raised when the end time leaves no room for every payout cycle.
+    InvalidEndTime,
+    /// Raised when the contribution cooldown divisor is zero.
+    InvalidCooldownDivisor,
 }
 
+/// The number of decimals of CCD, used when no decimals are configured.
+const CCD_DECIMALS: u8 = 6;
+
+/// The default delay before a backup finalizer can finalize, as in
+/// `init-param.json`.
+const DEFAULT_BACKUP_FINALIZER_DELAY: Duration = Duration::from_days(7);
+
+/// The default minimum number of members for closing joining early.
+const DEFAULT_MIN_MEMBERS: u64 = 2;
+
+/// The default window before a deadline in which reminders can be emitted.
+const DEFAULT_REMINDER_WINDOW: Duration = Duration::from_days(2);
+
+/// The default share of members, in basis points, needed to abort the club.
+const DEFAULT_ABORT_SUPERMAJORITY_BPS: u64 = 6666;
+
+/// The maximum number of members processed by a single batched call, to
+/// stay within the energy limit of a transaction.
+const MAX_BATCH_SIZE: u64 = 10;
+
+/// The default value the payment interval is divided by to get the
+/// cooldown between two contributions of the same member.
+const DEFAULT_CONTRIBUTION_COOLDOWN_DIVISOR: u64 = 2;
+
 // struct InitParameter {
 //     name: String,
 //     description: String,
@@ -209,7 +1188,7 @@ struct InitParameter {
     // creator: AccountAddress,
     /// The amount of money each member contributes to the Tanda
     contribution_amount: Amount,
-    /// The payout cycle for the Tanda
+    /// The payout cycle for the Tanda, at most one cycle per member
     payout_cycle: u64,
     /// The time when the Tanda will start using the RFC 3339 format (https://tools.ietf.org/html/rfc3339)
     start_time: Timestamp,
@@ -221,13 +1200,311 @@ struct InitParameter {
     penalty_amount: Amount,
     /// The maximum number of members allowed.
     max_contributors: u64,
+    /// How long after the end time a backup finalizer has to wait before finalizing.
+    backup_finalizer_delay: Duration,
+    /// The factor applied to the penalty deposit of members joining after the first payment interval.
+    late_join_multiplier: u64,
+    /// Where the deposit of a member who forfeits goes.
+    forfeit_destination: ForfeitDestination,
+    /// The number of decimals used to display amounts, defaults to 6 (CCD).
+    decimals: Option<u8>,
+    /// Whether members can prepay future cycles by sending a multiple of the contribution amount.
+    allow_prepay: bool,
+    /// The minimum number of members required before the creator can close joining early.
+    min_members: u64,
+    /// What happens to the balance left when the club is finalized.
+    end_of_life: EndOfLifePolicy,
+    /// How long before a contribution deadline reminders can be emitted.
+    reminder_window: Duration,
+    /// Payouts above this amount need the approval of the creator, if set.
+    cosign_threshold: Option<Amount>,
+    /// The non-refundable fee paid into the pot when joining. At most the
+    /// contribution amount.
+    join_fee: Amount,
+    /// Splits the rotation into sequential sub-rounds of this many members.
+    sub_round_size: Option<u64>,
+    /// The share of members, in basis points, needed to abort the club.
+    /// Between 1 and 10000.
+    abort_supermajority_bps: u64,
+    /// Caps the payout of each cycle, rolling the excess into later cycles.
+    max_payout_per_cycle: Option<Amount>,
+    /// Whether deadlines follow the global schedule or each member's join time.
+    deadline_mode: DeadlineMode,
+    /// Members with a shorter contribution streak are paid out after the
+    /// others. Zero disables the requirement.
+    min_streak: u64,
+    /// The share of the maximum number of members, in basis points, who
+    /// must have contributed to start the payout phase. Between 1 and 10000.
+    payout_quorum_bps: u64,
+    /// The number of cycles after joining during which a member is paid
+    /// out only after the others.
+    probation_cycles: u64,
+    /// The increase of the late penalty for every earlier default of the
+    /// member, in basis points of the penalty amount.
+    escalation_bps: u16,
+    /// Whether the name can no longer be changed once the first
+    /// contribution has been made.
+    lock_terms: bool,
+    /// How much a contribution for the final cycle may fall short. The
+    /// shortfall is taken from the member's deposit.
+    final_cycle_tolerance: Amount,
+    /// Where the remainder of sharing the balance equally goes when
+    /// finalizing.
+    dust_policy: DustPolicy,
+    /// Whether missed cycles are penalized automatically from the deposit,
+    /// instead of adding a penalty to late contributions.
+    auto_penalize: bool,
+    /// How long after the start time members have to wait before voting
+    /// to abort, giving the creator time to resolve issues.
+    abort_vote_delay: Duration,
+    /// Whether finalizing has to wait for the end time even when all
+    /// payout cycles completed earlier.
+    strict_finalize: bool,
+    /// The minimum time between two consecutive joins to the club, so a
+    /// single actor cannot take many slots with a burst of accounts.
+    min_join_gap: Duration,
+    /// The number of first joiners who pay the reduced early bird penalty
+    /// deposit instead of the penalty amount.
+    early_bird_count: u64,
+    /// The penalty deposit of the early birds. It cannot exceed the
+    /// penalty amount.
+    early_bird_penalty: Amount,
+    /// A stake every member locks when joining, on top of the penalty
+    /// deposit. It is returned when the club is finalized to members who
+    /// never defaulted on a cycle, and forfeited otherwise.
+    collateral_amount: Amount,
+    /// The smallest distributable pot that can be paid out, so early
+    /// payouts are not too small to be meaningful.
+    min_pot_for_payout: Amount,
+    /// The share of `max_contributors`, in basis points, at which joining
+    /// closes automatically, keeping the remaining slots for the waitlist.
+    soft_close_bps: Option<u16>,
+    /// The payment interval is divided by this value to get the cooldown
+    /// between two contributions of the same member.
+    contribution_cooldown_divisor: u64,
 }
 
+/// The parameter type for the contract function `joinTanda`.
 #[derive(Serialize, SchemaType, Clone, PartialEq)]
 pub struct JoinTandaParameter {
+    /// The penalty deposit the joining account agrees to pay, in micro CCD.
+    /// It has to match the deposit required at the time of joining, so the
+    /// account cannot be charged a different deposit than it expected.
     penalty_amount: u64,
 }
 
+/// The parameter type for paginated views.
+#[derive(Serialize, SchemaType, Clone, PartialEq)]
+pub struct PaginationParams {
+    /// The position of the first entry to return
+    offset: u64,
+    /// The maximum number of entries to return, at most `MAX_PAGE_SIZE`
+    limit: u64,
+}
+
+/// The parameter type for slashing a member's deposit.
+#[derive(Serialize, SchemaType, Clone, PartialEq)]
+pub struct SlashParameter {
+    /// The member whose deposit is slashed.
+    member: AccountAddress,
+    /// The amount taken from the deposit.
+    amount: Amount,
+}
+
+/// The maximum length of the club name, in bytes.
+const MAX_NAME_LENGTH: usize = 100;
+
+/// The maximum length of the metadata a member can attach, in bytes.
+const MAX_METADATA_LENGTH: usize = 64;
+
+/// The number of times a member can defer their payout.
+const MAX_DEFERRALS: u64 = 1;
+
+/// The denominator of shares expressed in basis points.
+const BPS_DENOMINATOR: u64 = 10_000;
+
+/// The number of times a failed payout can be retried before it is forfeited.
+const MAX_PAYOUT_RETRIES: u64 = 3;
+
+/// The all-zero account address, which never receives a payout.
+const NULL_ACCOUNT: AccountAddress = AccountAddress([0u8; 32]);
+
+/// The maximum number of entries returned by a paginated view.
+const MAX_PAGE_SIZE: u64 = 50;
+
+/// The version of the snapshot format returned by `exportSnapshot`.
+const SNAPSHOT_VERSION: u8 = 1;
+
+/// A versioned snapshot of the critical state of the club for off-chain
+/// backup and restore tooling. Members are in join order and the sets are
+/// in ascending address order, so identical state gives identical bytes.
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct Snapshot {
+    /// The version of the snapshot format
+    version: u8,
+    /// The name of the Tanda or Osusu club
+    name: String,
+    /// A brief description of the Tanda club
+    description: String,
+    /// The creator of the Tanda club address
+    creator: AccountAddress,
+    /// The amount of money each member contributes to the Tanda
+    contribution_amount: Amount,
+    /// The penalty amount to paid in addition to the contribution amount.
+    penalty_amount: Amount,
+    /// The payout cycle for the Tanda
+    payout_cycle: u64,
+    /// The time when the Tanda started or will start
+    start_time: Timestamp,
+    /// The time when the Tanda will be finalized
+    end_time: Timestamp,
+    /// Payment interval for the Tanda club.
+    time_interval: Duration,
+    /// The maximum number of members allowed.
+    max_contributors: u64,
+    /// State of the Tanda
+    tanda_state: TandaState,
+    /// The list of members who have joined the Tanda
+    members: Vec<(AccountAddress, u64)>,
+    /// The list of accounts that have made a contribution to the tanda
+    contributors: BTreeSet<AccountAddress>,
+    /// List of address that has withdrwan from the pot.
+    withdrawn_addresses: BTreeSet<AccountAddress>,
+    /// The current payout cycle
+    current_cycle: u64,
+    /// The list of accounts that have received payment after every cycle
+    completed_cycles: Vec<(u64, Vec<AccountAddress>)>,
+    /// The total amount of contributions made by all members
+    total_contributions: Amount,
+}
+
+/// The status of a member as returned by `getMemberStatus`.
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct MemberStatus {
+    /// The position of the member in join order
+    index: u64,
+    /// The refundable part of the member's penalty deposit
+    deposit: Amount,
+    /// Whether the member has paid for the current contribution cycle
+    contributed_this_cycle: bool,
+    /// Whether the member has received their payout
+    withdrawn: bool,
+    /// The opaque metadata attached by the member
+    metadata: Vec<u8>,
+}
+
+/// The optional features enabled for this instance, as returned by
+/// `getCapabilities`.
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct Capabilities {
+    /// Whether members can pay for several cycles at once
+    prepay: bool,
+    /// Whether large payouts need the approval of the creator
+    cosign: bool,
+    /// Whether joining costs a non-refundable fee
+    join_fee: bool,
+    /// Whether late joiners pay a higher deposit
+    late_join_multiplier: bool,
+    /// Whether the rotation is split into sub-rounds
+    sub_rounds: bool,
+    /// Whether the payout per cycle is capped
+    payout_cap: bool,
+    /// Whether a backup finalizer is set
+    backup_finalizer: bool,
+    /// Whether reminders can be emitted before deadlines
+    reminders: bool,
+}
+
+/// Who initialized this instance and when, as returned by `getProvenance`.
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct Provenance {
+    /// The account that sent the init transaction
+    init_origin: AccountAddress,
+    /// The slot time of the block the instance was initialized in
+    initialized_at: Timestamp,
+}
+
+/// The contributions, penalties and payouts of a member as returned by
+/// `getMemberLedger`.
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct MemberLedger {
+    /// The total contributed by the member, without penalties
+    contributed: Amount,
+    /// The penalty due if the member pays for the current cycle now
+    penalties_owed: Amount,
+    /// The total of late penalties paid by the member
+    penalties_paid: Amount,
+    /// The refundable part of the member's penalty deposit
+    deposit: Amount,
+    /// The total of the payouts credited to the member
+    withdrawn: Amount,
+}
+
+/// A payout made to a member.
+#[derive(Debug, Serialize, SchemaType, Clone, PartialEq)]
+pub struct PayoutRecord {
+    /// The cycle the payout was made for
+    cycle: u64,
+    /// The member who received the payout
+    receiver: AccountAddress,
+    /// The amount paid out
+    amount: Amount,
+    /// The time of the payout
+    timestamp: Timestamp,
+}
+
+/// The fees and penalties of the club as returned by `getFeeSchedule`.
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct FeeSchedule {
+    /// The non-refundable fee paid on joining
+    join_fee: Amount,
+    /// The penalty deposit paid on joining, also charged for late
+    /// contributions
+    penalty_amount: Amount,
+    /// The multiplier of the deposit for joining after the first interval
+    late_join_multiplier: u64,
+    /// The increase of the late penalty per earlier default, in basis points
+    escalation_bps: u16,
+    /// Whether missed cycles are penalized from the deposit
+    auto_penalize: bool,
+}
+
+/// The activity counters of the club as returned by `getMetrics`.
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct Metrics {
+    /// The number of members that joined
+    joins: u64,
+    /// The number of contributions made
+    contributions: u64,
+    /// The number of payouts made
+    payouts: u64,
+    /// The total of the late penalties collected
+    penalties_collected: Amount,
+    /// The current balance of the contract
+    balance: Amount,
+}
+
+/// A page of the payout history as returned by `getPayoutLedger`.
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct PayoutLedgerPage {
+    /// The payouts on this page, in order
+    rows: Vec<PayoutRecord>,
+    /// The offset of the next page, if there is one
+    next_offset: Option<u64>,
+}
+
+/// The status of the vote to abort the club as returned by
+/// `getAbortVoteStatus`.
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct VoteStatus {
+    /// The number of current members who voted to abort
+    votes: u64,
+    /// The number of votes needed to abort
+    threshold: u64,
+    /// Whether the club has been aborted
+    passed: bool,
+}
+
 /// The event is logged when a new (or replacement) vote is cast by an account.
 #[derive(Debug, Serialize, SchemaType)]
 pub struct TandaEvent {
@@ -235,12 +1512,33 @@ pub struct TandaEvent {
     user: AccountAddress,
 }
 
+/// The event is logged to remind members of an upcoming contribution deadline.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct ReminderEvent {
+    /// The contribution cycle the deadline belongs to.
+    cycle: u64,
+    /// The time by which the cycle has to be paid for.
+    deadline: Timestamp,
+}
+
+/// The event is logged when the creator renames the club.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct RenamedEvent {
+    /// The new name of the club.
+    name: String,
+}
+
 /// The event logged by this smart contract.
 #[derive(Debug, Serial, SchemaType)]
 pub enum Event {
     /// The event is logged when a new (or replacement) vote is cast by an
     /// account.
     Join(TandaEvent),
+    /// The event is logged when a reminder for a contribution deadline is
+    /// emitted, for off-chain notifiers to relay.
+    Reminder(ReminderEvent),
+    /// The event is logged when the club is renamed.
+    Renamed(RenamedEvent),
 }
 
 // Contract functions
@@ -271,21 +1569,84 @@ fn tanda_init<S: HasStateApi>(
     // }
     // .into_timestamp();
 
-    let withdrawal_start_time = now
-        .checked_add(param.time_interval.into())
-        .ok_or(Error::InvalidState)?;
-    // let test_duration = Duration::
+    validate_name(&param.name)?;
 
-    Ok(State {
-        name: param.name,
-        description: param.description,
-        creator: account,
-        tanda_state: TandaState::Open,
-        members: None,
-        contribution_amount: param.contribution_amount,
-        penalty_amount: param.penalty_amount,
-        total_contributions: concordium_std::Amount { micro_ccd: 0 },
-        payout_cycle: param.payout_cycle,
+    // A join fee above the contribution would price out members.
+    if param.join_fee > param.contribution_amount {
+        return Err(Error::InvalidJoinFee.into());
+    }
+
+    if param.sub_round_size == Some(0) {
+        return Err(Error::InvalidSubRoundSize.into());
+    }
+
+    validate_penalty_amount(
+        param.penalty_amount,
+        param.early_bird_count,
+        param.early_bird_penalty,
+    )?;
+
+    if param.abort_supermajority_bps == 0 || param.abort_supermajority_bps > BPS_DENOMINATOR {
+        return Err(Error::InvalidAbortThreshold.into());
+    }
+
+    if param.payout_quorum_bps == 0 || param.payout_quorum_bps > BPS_DENOMINATOR {
+        return Err(Error::InvalidPayoutQuorum.into());
+    }
+
+    if let Some(soft_close_bps) = param.soft_close_bps {
+        if soft_close_bps == 0 || u64::from(soft_close_bps) > BPS_DENOMINATOR {
+            return Err(Error::InvalidSoftClose.into());
+        }
+    }
+
+    if param.contribution_cooldown_divisor == 0 {
+        return Err(Error::InvalidCooldownDivisor.into());
+    }
+
+    // Every cycle pays out a different member, so members are never paid
+    // twice and a club needs at least one member per cycle. Without any
+    // cycle the club could be finalized right away without a payout.
+    if param.payout_cycle == 0 || param.payout_cycle > param.max_contributors {
+        return Err(Error::InvalidPayoutCycle.into());
+    }
+
+    // A zero interval would make every cycle and deadline collapse.
+    if param.time_interval.millis() == 0 {
+        return Err(Error::InvalidTimeInterval.into());
+    }
+
+    // Withdrawals start once all contribution cycles have passed.
+    let contribution_phase = param
+        .time_interval
+        .millis()
+        .checked_mul(param.payout_cycle)
+        .ok_or(Error::InvalidTimeInterval)?;
+    let withdrawal_start_time = param
+        .start_time
+        .checked_add(Duration::from_millis(contribution_phase))
+        .ok_or(Error::InvalidTimeInterval)?;
+    // let test_duration = Duration::
+
+    // Every member is paid out one interval apart after the contributions,
+    // which has to happen before the club ends.
+    let payout_phase_end = withdrawal_start_time
+        .checked_add(Duration::from_millis(contribution_phase))
+        .ok_or(Error::InvalidTimeInterval)?;
+    if payout_phase_end > param.end_time {
+        return Err(Error::InvalidEndTime.into());
+    }
+
+    Ok(State {
+        name: param.name,
+        description: param.description,
+        creator: account,
+        tanda_state: TandaState::Open,
+        members: None,
+        contribution_amount: param.contribution_amount,
+        penalty_amount: param.penalty_amount,
+        total_contributions: concordium_std::Amount { micro_ccd: 0 },
+        payout_cycle: param.payout_cycle,
         current_cycle: 0,
         start_time: param.start_time,
         end_time: param.end_time,
@@ -300,10 +1661,82 @@ fn tanda_init<S: HasStateApi>(
         withdrawal_phase_started: false,
         max_contributors: param.max_contributors,
         user_index: 0,
+        last_contribution_times: BTreeMap::new(),
+        finalized: false,
+        backup_finalizer: None,
+        backup_finalizer_delay: param.backup_finalizer_delay,
+        late_join_multiplier: param.late_join_multiplier,
+        deposits: BTreeMap::new(),
+        forfeit_destination: param.forfeit_destination,
+        last_payout_amount: Amount::zero(),
+        decimals: param.decimals.unwrap_or(CCD_DECIMALS),
+        allow_prepay: param.allow_prepay,
+        contributed_cycles: BTreeMap::new(),
+        min_members: param.min_members,
+        end_of_life: param.end_of_life,
+        withdrawable_balances: BTreeMap::new(),
+        skipped_receivers: Vec::new(),
+        reminder_window: param.reminder_window,
+        last_reminder_cycle: None,
+        cosign_threshold: param.cosign_threshold,
+        pending_withdrawal: None,
+        member_metadata: BTreeMap::new(),
+        join_fee: param.join_fee,
+        sub_round_size: param.sub_round_size,
+        sub_round: 0,
+        created_at: now,
+        refunds_complete: false,
+        abort_supermajority_bps: param.abort_supermajority_bps,
+        abort_votes: BTreeSet::new(),
+        aborted: false,
+        max_payout_per_cycle: param.max_payout_per_cycle,
+        total_paid_out: Amount::zero(),
+        deadline_mode: param.deadline_mode,
+        joined_at: BTreeMap::new(),
+        deferrals: BTreeMap::new(),
+        min_streak: param.min_streak,
+        member_contributions: BTreeMap::new(),
+        penalties_paid: BTreeMap::new(),
+        payouts_received: BTreeMap::new(),
+        payout_history: Vec::new(),
+        payout_quorum_bps: param.payout_quorum_bps,
+        probation_cycles: param.probation_cycles,
+        waitlist: Vec::new(),
+        escalation_bps: param.escalation_bps,
+        defaults: BTreeMap::new(),
+        frozen_order: Vec::new(),
+        join_count: 0,
+        contribution_count: 0,
+        payout_count: 0,
+        penalties_collected: Amount::zero(),
+        settled: false,
+        lock_terms: param.lock_terms,
+        terms_locked: false,
+        final_cycle_tolerance: param.final_cycle_tolerance,
+        dust_policy: param.dust_policy,
+        auto_penalize: param.auto_penalize,
+        swept_cycles: BTreeMap::new(),
+        carried_over: Vec::new(),
+        abort_vote_delay: param.abort_vote_delay,
+        strict_finalize: param.strict_finalize,
+        failed_payouts: BTreeMap::new(),
+        payout_retries: BTreeMap::new(),
+        min_join_gap: param.min_join_gap,
+        last_join_time: None,
+        early_bird_count: param.early_bird_count,
+        early_bird_penalty: param.early_bird_penalty,
+        collateral_amount: param.collateral_amount,
+        collaterals: BTreeMap::new(),
+        min_pot_for_payout: param.min_pot_for_payout,
+        activity: BTreeMap::new(),
+        soft_close_bps: param.soft_close_bps,
+        contribution_cooldown_divisor: param.contribution_cooldown_divisor,
     })
 }
 
 /// Enables a qualified user to join a Tanda club and pay penalty fee.
+/// The amount paid is the refundable penalty deposit plus the
+/// non-refundable join fee, which is added to the pot.
 /// Adds a new member to the Tanda club and associates their address with a unique user index.
 /// The user index is incremented each time a new member is added. If the maximum number of
 /// contributors has already been reached, the function returns an error.
@@ -321,6 +1754,11 @@ fn tanda_init<S: HasStateApi>(
 /// - The Tanda club has reached its maximum limit.
 /// - The Tanda state is closed.
 /// * The maximum number of contributors has already been reached.
+/// * The minimum gap since the last join has not passed yet.
+/// * The penalty amount in the parameter differs from the required deposit.
+///
+/// All checks are made before the state is changed, so a failed join
+/// leaves no partial member behind and the payment is returned.
 ///
 #[receive(
     contract = "dthrift",
@@ -356,7 +1794,7 @@ fn join_tanda<S: HasStateApi>(
     // Check if the Tanda has reached its maximum limit.
     let members = &mut host.state().members.as_ref().map_or(0, |v| v.len());
     ensure!(
-        *members as u64 == host.state().max_contributors,
+        (*members as u64) < host.state().max_contributors,
         Error::MaximumReached
     );
 
@@ -368,8 +1806,19 @@ fn join_tanda<S: HasStateApi>(
         }
     }
 
+    // Check that joins are spaced by the minimum gap.
+    if let Some(last_join_time) = host.state().last_join_time {
+        let earliest_time = last_join_time
+            .checked_add(host.state().min_join_gap)
+            .ok_or(Error::InvalidState)?;
+        ensure!(ctx.metadata().slot_time() >= earliest_time, Error::JoinTooSoon);
+    }
+
     // Check if the penalty amount is valid
-    if amount != host.state().penalty_amount {
+    let required_amount = host
+        .state()
+        .required_join_amount(ctx.metadata().slot_time())?;
+    if amount != required_amount {
         return Err(Error::InvalidPenaltyAmount);
     }
 
@@ -379,30 +1828,44 @@ fn join_tanda<S: HasStateApi>(
         Address::Contract(_) => return Err(Error::ContractMember),
     };
 
-    // Update penalty_amount
+    // The new member's index must keep the indices unique and increasing.
+    let new_user_index = host.state().user_index + 1;
+    let mut indexed_members = host.state().members.clone().unwrap_or_default();
+    indexed_members.push((acc, new_user_index));
+    ensure!(validate_member_indices(&indexed_members), Error::InternalError);
+
     let param: JoinTandaParameter = ctx.parameter_cursor().get()?;
-    let penalty_amount = param.penalty_amount;
-    host.state_mut().penalty_amount += concordium_std::Amount {
-        micro_ccd: penalty_amount,
-    };
+    let required_deposit = host.state().required_deposit(ctx.metadata().slot_time())?;
+    ensure!(
+        param.penalty_amount == required_deposit.micro_ccd,
+        Error::InvalidPenaltyAmount
+    );
 
-    // Update the user_index count
-    let new_user_index = host.state_mut().user_index + 1;
-    host.state_mut().user_index = new_user_index;
+    // All checks have passed; only from here on is the state changed.
 
-    // Update the members list
-    let new_user_address = acc;
+    // Update the user_index count and the members list
+    host.state_mut().user_index = new_user_index;
+    host.state_mut().members = Some(indexed_members);
 
-    let new_member = (new_user_address, new_user_index);
-    if let Some(mut members) = host.state_mut().members.take() {
-        members.push(new_member);
-        host.state_mut().members = Some(members.to_vec());
-    } else {
-        host.state_mut().members = Some(vec![new_member]);
+    // Record the deposit and collateral paid by the member; the join fee
+    // goes to the pot.
+    let join_fee = host.state().join_fee;
+    let collateral = host.state().collateral_amount;
+    host.state_mut().deposits.insert(acc, amount - join_fee - collateral);
+    if collateral > Amount::zero() {
+        host.state_mut().collaterals.insert(acc, collateral);
     }
+    host.state_mut().total_contributions += join_fee;
+    host.state_mut().joined_at.insert(acc, ctx.metadata().slot_time());
+    host.state_mut().last_join_time = Some(ctx.metadata().slot_time());
+    host.state_mut().join_count += 1;
 
-    //
+    // Close joining once the soft close share of the slots is taken.
+    if host.state().reached_soft_close() {
+        set_tanda_state(host.state_mut(), TandaState::Closed)?;
+    }
 
+    record_activity(host.state_mut(), "joinTanda", ctx.metadata().slot_time());
     Ok(())
 }
 
@@ -413,6 +1876,12 @@ fn join_tanda<S: HasStateApi>(
 /// amount to the total contributions, updates the member's
 /// contribution, and schedules the next receiver of the Tanda payout.
 ///
+/// A contribution pays for the current contribution cycle. When prepaying
+/// is allowed, a multiple of the contribution amount pays for as many
+/// cycles, starting after the last cycle already paid for. A member who
+/// did not pay for the previous cycle must add the penalty amount, which
+/// goes to the pot and escalates with every earlier default.
+///
 /// # Arguments
 ///
 /// * `ctx` - The context object that provides access to the current state and other data.
@@ -423,9 +1892,13 @@ fn join_tanda<S: HasStateApi>(
 /// This function will return an error if:
 ///
 /// * The Tanda club is already closed.
+/// * The Tanda club has completed all its payout cycles.
 /// * The maximum number of members has already been reached.
 /// * The member has already joined the Tanda club.
 /// * The contribution amount is less than the minimum required amount.
+/// * The member is late and the amount does not include the penalty.
+/// * The member has already paid for the current cycle.
+/// * The payment covers cycles beyond the last payout cycle.
 ///
 #[receive(
     contract = "dthrift",
@@ -442,16 +1915,57 @@ fn contribute<S: HasStateApi>(
     amount: Amount,
     logger: &mut impl HasLogger,
 ) -> Result<(), Error> {
+    // A completed club takes no more contributions.
+    ensure!(
+        host.state().tanda_state != TandaState::Completed,
+        Error::AlreadyFinished
+    );
+
+    // Nobody can contribute to a club without members.
+    let has_members = host.state().members.as_ref().map_or(false, |members| !members.is_empty());
+    ensure!(has_members, Error::NotJoined);
+
+    // Penalize the cycles that ended unpaid before taking the contribution.
+    sweep_defaults(host.state_mut(), ctx.metadata().slot_time());
+
     // Check that the contribution amount is greater than zero
     if amount <= (concordium_std::Amount { micro_ccd: 0 }) {
         return Err(Error::InvalidContributionAmount);
     }
 
-    // Check that the contribution amount is equal to the set contribution amount
-    let expected_contribution = host.state().contribution_amount;
-    if amount != expected_contribution {
+    // A member who missed the previous cycle pays the penalty on top of the
+    // contribution.
+    let cycle = host
+        .state()
+        .member_cycle(&ctx.invoker(), ctx.metadata().slot_time());
+    let late = host.state().is_late(&ctx.invoker(), cycle);
+    let penalty = if late {
+        host.state().late_penalty(&ctx.invoker())?
+    } else {
+        Amount::zero()
+    };
+    let base_micro_ccd = match amount.micro_ccd.checked_sub(penalty.micro_ccd) {
+        Some(base_micro_ccd) => base_micro_ccd,
+        None => return Err(Error::Penalized),
+    };
+
+    // Check that the contribution amount is equal to the expected contribution,
+    // or a multiple of it when prepaying is allowed.
+    let expected_contribution = expected_contribution(host.state(), cycle);
+    let top_up = final_cycle_top_up(host.state(), &ctx.invoker(), cycle, base_micro_ccd);
+    let cycles_paid = if base_micro_ccd + top_up.micro_ccd == expected_contribution.micro_ccd {
+        1
+    } else if host.state().allow_prepay
+        && expected_contribution.micro_ccd > 0
+        && base_micro_ccd > 0
+        && base_micro_ccd % expected_contribution.micro_ccd == 0
+    {
+        base_micro_ccd / expected_contribution.micro_ccd
+    } else if late {
+        return Err(Error::Penalized);
+    } else {
         return Err(Error::InvalidContributionAmount);
-    }
+    };
 
     // Get the current time
     let current_time = ctx.metadata().slot_time();
@@ -467,9 +1981,11 @@ fn contribute<S: HasStateApi>(
         return Err(Error::TandaClosed);
     }
 
-    // Check if the club is still open
+    // Check that the club is still in its contribution phase; a club that is
+    // closed for joining still accepts contributions.
+    let tanda_state = host.state().tanda_state;
     ensure!(
-        host.state().tanda_state != TandaState::Closed,
+        tanda_state == TandaState::Open || tanda_state == TandaState::Closed,
         Error::TandaClosed
     );
 
@@ -485,26 +2001,118 @@ fn contribute<S: HasStateApi>(
 
     // Ensure that the address/account is a member; should join first+
     let sender_address = ctx.invoker();
-    let existing_members = host.state_mut().members.take().unwrap_or_default();
-    if existing_members
-        .iter()
-        .any(|(address, _)| address == &sender_address)
-    {
+    if !host.state().is_member(&sender_address) {
         return Err(Error::NotJoined);
     }
 
+    // Ensure the member is not contributing again before the cooldown has elapsed.
+    let cooldown = Duration::from_millis(
+        host.state().time_interval.millis() / host.state().contribution_cooldown_divisor,
+    );
+    if let Some(last_contribution_time) = host.state().last_contribution_times.get(&sender_address) {
+        if current_time
+            .duration_since(*last_contribution_time)
+            .map_or(false, |dur| dur < cooldown)
+        {
+            return Err(Error::ContributionCooldown);
+        }
+    }
+
+    // Determine the cycles paid for: the current cycle, or the cycles after
+    // the last one already paid for when prepaying.
+    ensure!(cycle < host.state().payout_cycle, Error::TandaClosed);
+    let last_paid_cycle = host
+        .state()
+        .contributed_cycles
+        .get(&sender_address)
+        .and_then(|cycles| cycles.iter().next_back().copied());
+    let first_cycle = match last_paid_cycle {
+        Some(last_paid_cycle) if last_paid_cycle >= cycle => {
+            ensure!(host.state().allow_prepay, Error::AlreadyContributed);
+            last_paid_cycle + 1
+        }
+        _ => cycle,
+    };
+    let end_cycle = first_cycle + cycles_paid;
+    ensure!(
+        end_cycle <= host.state().payout_cycle,
+        Error::InvalidContributionAmount
+    );
+    host.state_mut()
+        .contributed_cycles
+        .entry(sender_address)
+        .or_insert_with(BTreeSet::new)
+        .extend(first_cycle..end_cycle);
+
     // Add to contributors set
     host.state_mut().contributors.insert(sender_address);
     // contributors.insert(sender_address);
     // host.state_mut().contributors = Some(contributors);
 
+    // A short final contribution is topped up from the deposit.
+    if top_up > Amount::zero() {
+        if let Some(deposit) = host.state_mut().deposits.get_mut(&sender_address) {
+            *deposit -= top_up;
+        }
+    }
+
     // Increase the total_contributions
-    let new_total_contributions = host.state_mut().total_contributions + amount;
+    let new_total_contributions = host.state_mut().total_contributions + amount + top_up;
     host.state_mut().total_contributions = new_total_contributions;
 
+    // Remember when the member contributed.
+    host.state_mut()
+        .last_contribution_times
+        .insert(sender_address, current_time);
+
+    // Keep the member's ledger.
+    let state = host.state_mut();
+    *state
+        .member_contributions
+        .entry(sender_address)
+        .or_insert_with(Amount::zero) += amount - penalty + top_up;
+    if penalty > Amount::zero() {
+        *state
+            .penalties_paid
+            .entry(sender_address)
+            .or_insert_with(Amount::zero) += penalty;
+        state.penalties_collected += penalty;
+    }
+    state.contribution_count += 1;
+    if state.lock_terms {
+        state.terms_locked = true;
+    }
+    if late {
+        *state.defaults.entry(sender_address).or_insert(0) += 1;
+    }
+
+    record_activity(host.state_mut(), "contribute", ctx.metadata().slot_time());
     Ok(())
 }
 
+/// The part of the deposit that tops up a contribution of `base_micro_ccd`
+/// for the final cycle, when it falls short by no more than the final
+/// cycle tolerance. Zero in every other case.
+fn final_cycle_top_up(
+    state: &State,
+    member: &AccountAddress,
+    cycle: u64,
+    base_micro_ccd: u64,
+) -> Amount {
+    let expected = expected_contribution(state, cycle).micro_ccd;
+    let shortfall = expected.saturating_sub(base_micro_ccd);
+    let deposit = state.deposits.get(member).map_or(0, |deposit| deposit.micro_ccd);
+    if cycle + 1 == state.payout_cycle
+        && base_micro_ccd > 0
+        && shortfall <= state.final_cycle_tolerance.micro_ccd
+        && shortfall <= deposit
+    {
+        Amount::from_micro_ccd(shortfall)
+    } else {
+        Amount::zero()
+    }
+}
+
 /// Withdraws the current pot for the Tanda club.
 ///
 /// # Arguments
@@ -515,7 +2123,16 @@ fn contribute<S: HasStateApi>(
 ///
 /// * `MemberNotFound` - When the account attempting to withdraw is not a member of the Tanda club.
 /// * `TandaClosed` - When the Tanda club is not open for withdrawals.
+/// * `InvalidState` - When the withdrawal phase has not started yet or no receiver is selected.
+/// * `NotAuthorized` - When the account is not the next receiver.
+/// * `CosignRequired` - When the payout exceeds the co-sign threshold.
+/// * `InsufficientBalance` - When the balance does not cover the payout.
 ///
+/// Only the member selected as next receiver can withdraw, once per cycle.
+/// The payout is credited to the member, who transfers it to their account
+/// by calling `claim`. Payouts above the co-sign threshold go through
+/// `requestWithdraw` and `approveWithdraw` instead.
+/// The first withdrawal of a `Pending` club moves it to `InProgress`.
 #[receive(
     contract = "dthrift",
     name = "withdraw",
@@ -534,8 +2151,36 @@ fn withdraw<S: HasStateApi>(
     // Get the current time.
     let now = ctx.metadata().slot_time();
 
+    // Move on to the next cycle if it is due.
+    let _ = advance_cycle(host.state_mut(), now);
+
+    // Ensure that the sender is an account
+    let acc = match ctx.sender() {
+        Address::Account(acc) => acc,
+        Address::Contract(_) => return Err(Error::ContractMember),
+    };
+
+    let sender_address = ctx.invoker();
+    let cycle = ensure_can_withdraw(host.state(), &sender_address, now)?;
+
+    // Payouts above the co-sign threshold need the approval of the creator.
+    ensure!(!host.state().needs_cosign(), Error::CosignRequired);
+    ensure_payout_covered(host)?;
+
+    pay_out(host.state_mut(), sender_address, cycle, now)?;
+    record_activity(host.state_mut(), "withdraw", ctx.metadata().slot_time());
+    Ok(())
+}
+
+/// Checks that `receiver` can withdraw the payout of the current cycle at
+/// `now` and returns that cycle.
+fn ensure_can_withdraw(
+    state: &State,
+    receiver: &AccountAddress,
+    now: Timestamp,
+) -> Result<u64, Error> {
     // Check if the current time is after the end time of the Tanda.
-    if now >= host.state().end_time {
+    if now >= state.end_time {
         return Err(Error::AlreadyFinalized);
     }
 
@@ -558,65 +2203,237 @@ fn withdraw<S: HasStateApi>(
     // }
 
     if now
-        .duration_since(host.state().last_withdrawal_time)
-        .map_or(false, |dur| dur < host.state().time_interval)
+        .duration_since(state.last_withdrawal_time)
+        .map_or(false, |dur| dur < state.time_interval)
     {
         return Err(Error::WithdrawalTimeNotReached);
     }
 
     // Check if the club is closed
-    if host.state().tanda_state == TandaState::Closed {
+    if state.tanda_state == TandaState::Closed {
         return Err(Error::TandaClosed);
     }
 
-    // Ensure that the sender is an account
-    let acc = match ctx.sender() {
-        Address::Account(acc) => acc,
-        Address::Contract(_) => return Err(Error::ContractMember),
-    };
+    // Withdrawals are only possible once the withdrawal phase has started.
+    if state.tanda_state != TandaState::Pending && state.tanda_state != TandaState::InProgress {
+        return Err(Error::InvalidState);
+    }
+
+    // Ensure a receiver has been selected for the current cycle.
+    let next_receiver = state.next_receiver.ok_or(Error::InvalidState)?;
+
+    // Ensure the current cycle has not been paid out yet.
+    let cycle = state.current_cycle;
+    if state.is_cycle_paid(cycle) {
+        return Err(Error::WithdrawalTimeNotReached);
+    }
 
     // Ensure that the address/account is a member; should join first+
-    let sender_address = ctx.invoker();
-    let existing_members = host.state_mut().members.take().unwrap_or_default();
-    if existing_members
-        .iter()
-        .any(|(address, _)| address == &sender_address)
-    {
+    if !state.is_member(receiver) {
         return Err(Error::NotJoined);
     }
 
     // If the address has not contributed, they cannot withdraw
-    if !host.state().contributors.contains(&sender_address) {
+    if !state.contributors.contains(receiver) {
         return Err(Error::NotContributor);
     }
 
     // Check if the sender has already withdrawn
-    if host.state().withdrawn_addresses.contains(&sender_address) {
+    if state.withdrawn_addresses.contains(receiver) {
         return Err(Error::AlreadyWithdrawn);
     }
 
+    // Only the member next in line can withdraw.
+    if *receiver != next_receiver {
+        return Err(Error::NotAuthorized);
+    }
+
+    // Hold the payout until the pot reaches the minimum.
+    if state.distributable_pot() < state.min_pot_for_payout {
+        return Err(Error::InsufficientBalance);
+    }
+    Ok(cycle)
+}
+
+/// Checks that a payout pushed to `target` goes to a well-formed member
+/// account. Payouts only ever go to members, so any other target means the
+/// state is corrupted. The contract itself cannot be a target, as payouts
+/// are only made to account addresses.
+///
+/// Only `approveWithdraw` and `retryPayout` check this, as only they take
+/// their target from a stored receiver slot. `claim` and `leaveWaitlist`
+/// pay the sender, finalizing pays the listed members and the finalizer,
+/// and forfeited deposits go to the creator, who need not be a member.
+fn ensure_payout_target(state: &State, target: &AccountAddress) -> Result<(), Error> {
+    ensure!(
+        *target != NULL_ACCOUNT && state.is_member(target),
+        Error::InvalidAddress
+    );
+    Ok(())
+}
+
+/// Checks that the balance of the contract covers `amount`.
+fn ensure_balance_covers<S: HasStateApi>(
+    host: &impl HasHost<State, StateApiType = S>,
+    amount: Amount,
+) -> Result<(), Error> {
+    ensure!(host.self_balance() >= amount, Error::InsufficientBalance);
+    Ok(())
+}
+
+/// Checks that the balance of the contract covers the payout of the
+/// current cycle on top of the payouts not claimed yet.
+fn ensure_payout_covered<S: HasStateApi>(
+    host: &impl HasHost<State, StateApiType = S>,
+) -> Result<(), Error> {
+    let owed = host
+        .state()
+        .unclaimed_total()
+        .micro_ccd
+        .checked_add(host.state().current_payout().micro_ccd)
+        .ok_or(Error::InsufficientBalance)?;
+    ensure_balance_covers(host, Amount::from_micro_ccd(owed))
+}
+
+/// Pays out the given cycle to `receiver` by crediting the payout to be
+/// claimed separately, and selects the next receiver.
+fn pay_out(
+    state: &mut State,
+    receiver: AccountAddress,
+    cycle: u64,
+    now: Timestamp,
+) -> Result<(), Error> {
+    // Calculate when the next payout is due.
+    let next_withdrawal_time = now
+        .checked_add(state.time_interval)
+        .ok_or(Error::InvalidState)?;
+
     // Add to withdrawn set
-    host.state_mut().withdrawn_addresses.insert(sender_address);
+    state.withdrawn_addresses.insert(receiver);
+
+    // Credit the payout to the address, to be claimed separately.
+    let payout = state.current_payout();
+    *state
+        .withdrawable_balances
+        .entry(receiver)
+        .or_insert_with(Amount::zero) += payout;
+    state.last_payout_amount = payout;
+    state.total_paid_out += payout;
+    *state
+        .payouts_received
+        .entry(receiver)
+        .or_insert_with(Amount::zero) += payout;
+    state.payout_history.push(PayoutRecord {
+        cycle,
+        receiver,
+        amount: payout,
+        timestamp: now,
+    });
+    state.payout_count += 1;
+
+    // Update the last withdrawal time and when the next payout is due.
+    state.last_withdrawal_time = now;
+    state.next_withdrawal_time = next_withdrawal_time;
+
+    // Record the payout for the current cycle and select the next receiver.
+    state.completed_cycles.push((cycle, vec![receiver]));
+    reselect_receiver(state);
+
+    // The first withdrawal starts the payouts.
+    if state.tanda_state == TandaState::Pending {
+        set_tanda_state(state, TandaState::InProgress)?;
+    }
+    Ok(())
+}
+
+/// Requests the payout of the current cycle when it exceeds the co-sign
+/// threshold. The payout is made once the creator approves it with
+/// `approveWithdraw`. The same conditions as for `withdraw` apply.
+#[receive(
+    contract = "dthrift",
+    name = "requestWithdraw",
+    mutable,
+    error = "Error"
+)]
+fn request_withdraw<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<(), Error> {
+    let now = ctx.metadata().slot_time();
+
+    // Move on to the next cycle if it is due.
+    let _ = advance_cycle(host.state_mut(), now);
+
+    // Ensure that the sender is an account
+    let acc = match ctx.sender() {
+        Address::Account(acc) => acc,
+        Address::Contract(_) => return Err(Error::ContractMember),
+    };
+    ensure_can_withdraw(host.state(), &acc, now)?;
+
+    host.state_mut().pending_withdrawal = Some(acc);
+    record_activity(host.state_mut(), "requestWithdraw", ctx.metadata().slot_time());
+    Ok(())
+}
+
+/// Approves the pending withdrawal request and pays out the current cycle
+/// to the requesting member. The conditions for withdrawing are checked
+/// again at approval time. Only the creator can call this function.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * The caller is not the creator.
+/// * There is no pending withdrawal request.
+/// * The pending withdrawal is not for a member.
+/// * The requesting member can no longer withdraw.
+#[receive(
+    contract = "dthrift",
+    name = "approveWithdraw",
+    mutable,
+    error = "Error"
+)]
+fn approve_withdraw<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<(), Error> {
+    // Ensure that the caller is the owner of the contract
+    let owner = host.state().creator;
+    ensure!(
+        ctx.sender() == Address::Account(owner),
+        Error::NotAuthorized
+    );
 
-    // Send total contribution amount to the address
+    let receiver = host
+        .state()
+        .pending_withdrawal
+        .ok_or(Error::NoPendingWithdrawal)?;
+    ensure_payout_target(host.state(), &receiver)?;
+    let now = ctx.metadata().slot_time();
 
-    let total_contribution = host.state().total_contributions;
-    host.invoke_transfer(&ctx.invoker(), total_contribution)
-        .unwrap_abort();
+    // Move on to the next cycle if it is due.
+    let _ = advance_cycle(host.state_mut(), now);
+    let cycle = ensure_can_withdraw(host.state(), &receiver, now)?;
+    ensure_payout_covered(host)?;
 
-    // Update the last withdrawal time.
-    host.state_mut().last_withdrawal_time = now;
+    host.state_mut().pending_withdrawal = None;
+    pay_out(host.state_mut(), receiver, cycle, now)?;
+    record_activity(host.state_mut(), "approveWithdraw", ctx.metadata().slot_time());
     Ok(())
 }
 
 /// This function starts the withdrawal phase for the Tanda club.
 /// It checks if the Tanda club has reached its maximum number
-/// of members and if all members have made a contribution.
+/// of members and if enough members have made a contribution to
+/// reach the payout quorum; members who have not contributed are
+/// left out of the rotation.
 /// It also checks if the current time is after the withdrawal
 /// interval for the Tanda club. If these conditions are met,
 /// the function changes the state of the Tanda club to Pending,
 /// and schedules the first payout cycle by setting the first
-/// receiver of the payout.
+/// receiver of the payout. The club stays Pending until the
+/// first withdrawal is made.
 ///
 /// # Arguments
 ///
@@ -627,8 +2444,7 @@ fn withdraw<S: HasStateApi>(
 /// This function will return an error if:
 ///
 /// * The Tanda club is already closed.
-/// * The maximum number of members has not been reached yet.
-/// * Not all members have made a contribution yet.
+/// * Fewer members than the payout quorum have made a contribution yet.
 /// * The current time is before the withdrawal interval for the Tanda club.
 #[receive(
     contract = "dthrift",
@@ -654,8 +2470,8 @@ fn start_withdrawal_phase<S: HasStateApi>(
         return Err(Error::WithdrawalPhaseAlreadyStarted);
     }
 
-    // Ensure all members have contributed.
-    if host.state().contributors.len() != host.state().max_contributors as usize {
+    // Ensure enough members have contributed.
+    if !host.state().has_payout_quorum() {
         return Err(Error::ContributorsNotComplete);
     }
 
@@ -665,27 +2481,47 @@ fn start_withdrawal_phase<S: HasStateApi>(
         return Err(Error::WithdrawalIntervalNotReached);
     }
 
-    // Ensure the Tanda is in the InProgress state.
-    if host.state().tanda_state != TandaState::InProgress {
+    // Ensure the Tanda is still in its contribution phase.
+    let tanda_state = host.state().tanda_state;
+    if tanda_state != TandaState::Open && tanda_state != TandaState::Closed {
         return Err(Error::InvalidState);
     }
 
-    // Set the Tanda state to Pending.
-    host.state_mut().tanda_state = TandaState::Pending;
-
     // set the next_withdrawal_time
     // let withdrawal_start_time = now
     //     .checked_add(host.state_mut().time_interval.into())
     //     .ok_or(Error::InvalidState)?;
 
-    // Calculate the next withdrawal time.
-    let withdrawal_interval: Duration = host.state().time_interval.into();
-    let next_withdrawal_time =
-        host.state().withdrawal_start_time.timestamp_millis() + withdrawal_interval.millis();
-    host.state_mut().next_withdrawal_time = Timestamp::from_timestamp_millis(next_withdrawal_time);
+    // The contribution phase is over; penalize the cycles left unpaid.
+    sweep_defaults(host.state_mut(), now);
+
+    // Set the Tanda state to Pending. The first payout is due as soon as
+    // the payout phase opens.
+    set_tanda_state(host.state_mut(), TandaState::Pending)?;
+    let state = host.state_mut();
+    state.next_withdrawal_time = state.withdrawal_start_time;
+
+    // Members who have not contributed are left out of the rotation.
+    let non_contributors: Vec<AccountAddress> = state
+        .members
+        .iter()
+        .flatten()
+        .map(|(address, _)| *address)
+        .filter(|address| !state.contributors.contains(address))
+        .filter(|address| !state.skipped_receivers.contains(address))
+        .collect();
+    state.carried_over.extend(non_contributors.iter().copied());
+    state.skipped_receivers.extend(non_contributors);
+
+    // Freeze the rotation order and select the first receiver of the payout.
+    let frozen_order = rotation_order(host.state());
+    host.state_mut().frozen_order = frozen_order;
+    let next_receiver = select_next_receiver(host.state());
+    host.state_mut().next_receiver = next_receiver;
 
     // Mark the withdrawal phase as started.
     host.state_mut().withdrawal_phase_started = true;
+    record_activity(host.state_mut(), "start_withdrawal_phase", ctx.metadata().slot_time());
     Ok(())
 }
 
@@ -727,13 +2563,7 @@ fn withdraw_penalty_amount<S: HasStateApi>(
 ) -> Result<(), Error> {
     // Ensure the caller is a Tanda member.
     let sender_address = ctx.invoker();
-    let existing_members = host.state_mut().members.take().unwrap_or_default();
-    if existing_members
-        .iter()
-        .any(|(address, _)| address == &sender_address)
-    {
-        return Err(Error::NotJoined);
-    }
+    ensure!(host.state().is_member(&sender_address), Error::NotJoined);
 
     // Ensure the member has not already withdrawn their penalty amount.
     // if host.state().members[&sender_address].penalty_amount == 0 {
@@ -744,19 +2574,6035 @@ fn withdraw_penalty_amount<S: HasStateApi>(
 
     //
 
+    record_activity(host.state_mut(), "withdraw_penalty_amount", ctx.metadata().slot_time());
     Ok(())
 }
 
-// A function to Start a new contribution phase
+/// Finalizes the Tanda club after its final payout cycle and handles
+/// the remaining balance of the contract according to the end of life
+/// policy: it is shared equally among the members, with any indivisible
+/// remainder staying in the contract or going to the finalizer depending
+/// on the dust policy, sent to the creator, or left locked in the
+/// contract. Any member can finalize the club once all payout cycles are complete.
+/// The backup finalizer, if set by the creator, can finalize the club
+/// without being a member, but only once the backup finalizer delay has
+/// passed after the end time. This can be done only once.
+///
+/// Penalty deposits are kept out of the remaining balance, to be refunded
+/// with `refundBatch`. Large clubs can instead finalize over several
+/// transactions with `finalizeRefundBatch` and `finalizeComplete`.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * The Tanda club is already finalized.
+/// * The sender is a contract.
+/// * Not all payout cycles are complete.
+/// * Finalization is strict and the end time has not been reached.
+/// * The sender is neither a member nor the backup finalizer.
+/// * The backup finalizer acts before its delay has passed.
+#[receive(contract = "dthrift", name = "finalize", mutable, error = "Error")]
+fn finalize<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<(), Error> {
+    // Ensure the club is finalized only once.
+    ensure!(!host.state().finalized, Error::AlreadyFinalized);
+    ensure_can_finalize(ctx, host)?;
 
-/// View function that returns the content of the state.
-#[receive(contract = "dthrift", name = "view", return_value = "State")]
-fn view<'b, S: HasStateApi>(
-    _ctx: &impl HasReceiveContext,
-    host: &'b impl HasHost<State, StateApiType = S>,
-) -> ReceiveResult<&'b State> {
-    Ok(host.state())
+    set_tanda_state(host.state_mut(), TandaState::Completed)?;
+    host.state_mut().finalized = true;
+    release_collateral(host.state_mut());
+    distribute_remainder(host, ctx.invoker());
+    record_activity(host.state_mut(), "finalize", ctx.metadata().slot_time());
+    Ok(())
 }
 
-#[concordium_cfg_test]
-mod tests {}
+/// Returns the collateral of the members who never defaulted on a cycle by
+/// crediting it, to be claimed with `claim`. The collateral of defaulters
+/// is forfeited and handled with the remaining balance.
+fn release_collateral(state: &mut State) {
+    let collaterals = core::mem::take(&mut state.collaterals);
+    for (member, collateral) in collaterals {
+        if state.defaults.get(&member).copied().unwrap_or(0) == 0 {
+            *state
+                .withdrawable_balances
+                .entry(member)
+                .or_insert_with(Amount::zero) += collateral;
+        }
+    }
+}
+
+/// Checks that the sender can finalize the club, moving past the last
+/// cycle first if it is due.
+fn ensure_can_finalize<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<(), Error> {
+    // Ensure that the sender is an account
+    let acc = match ctx.sender() {
+        Address::Account(acc) => acc,
+        Address::Contract(_) => return Err(Error::ContractMember),
+    };
+
+    // Move past the last cycle if it is due.
+    let _ = advance_cycle(host.state_mut(), ctx.metadata().slot_time());
+
+    // Ensure all payout cycles are complete.
+    ensure!(
+        host.state().current_cycle >= host.state().payout_cycle,
+        Error::NotStarted
+    );
+
+    // Under strict finalization, also wait for the end time.
+    ensure!(
+        host.state().end_time_allows_finalize(ctx.metadata().slot_time()),
+        Error::NotStarted
+    );
+
+    // Members can finalize right away, the backup finalizer only after its delay.
+    if !host.state().is_member(&acc) {
+        ensure!(
+            host.state().backup_finalizer == Some(acc),
+            Error::NotJoined
+        );
+        let earliest_time = host
+            .state()
+            .end_time
+            .checked_add(host.state().backup_finalizer_delay)
+            .ok_or(Error::InvalidState)?;
+        ensure!(
+            ctx.metadata().slot_time() >= earliest_time,
+            Error::FinalizationDelayNotReached
+        );
+    }
+    Ok(())
+}
+
+/// The remaining balance of the contract, without the payouts members have
+/// not claimed yet and the deposits not refunded yet.
+fn remaining_balance<S: HasStateApi>(host: &impl HasHost<State, StateApiType = S>) -> Amount {
+    let reserved = host.state().unclaimed_total().micro_ccd
+        + host.state().deposits_total().micro_ccd;
+    Amount::from_micro_ccd(host.self_balance().micro_ccd.saturating_sub(reserved))
+}
+
+/// The total amount finalizing pays out of the remaining balance under the
+/// end of life policy. Members whose account no longer exists are skipped
+/// when paying, so the amount actually transferred can be lower.
+fn remainder_payout<S: HasStateApi>(host: &impl HasHost<State, StateApiType = S>) -> Amount {
+    let balance = remaining_balance(host);
+    match host.state().end_of_life {
+        EndOfLifePolicy::DistributeEqually => {
+            let members = host.state().members.as_ref().map_or(0, |v| v.len()) as u64;
+            match equal_share(balance, members) {
+                Some(_) => balance,
+                None => Amount::zero(),
+            }
+        }
+        EndOfLifePolicy::ToCreator => balance,
+        EndOfLifePolicy::Burn => Amount::zero(),
+    }
+}
+
+/// Handles the remaining balance according to the end of life policy,
+/// keeping the payouts members have not claimed yet and the deposits not
+/// refunded yet. The remainder of an equal split goes to `finalizer` under
+/// the finalizer dust policy, and to the member who joined first otherwise.
+fn distribute_remainder<S: HasStateApi>(
+    host: &mut impl HasHost<State, StateApiType = S>,
+    finalizer: AccountAddress,
+) {
+    let balance = remaining_balance(host);
+    let end_of_life = host.state().end_of_life;
+    match end_of_life {
+        EndOfLifePolicy::DistributeEqually => {
+            let members: Vec<AccountAddress> = host
+                .state()
+                .members
+                .iter()
+                .flatten()
+                .map(|(address, _)| *address)
+                .collect();
+            if let Some(share) = equal_share(balance, members.len() as u64) {
+                let dust = balance - share * members.len() as u64;
+                let to_finalizer = host.state().dust_policy == DustPolicy::Finalizer;
+                for (position, member) in members.iter().enumerate() {
+                    let amount = if position == 0 && !to_finalizer {
+                        share + dust
+                    } else {
+                        share
+                    };
+                    if amount > Amount::zero() && !transfer_or_skip(host, member, amount) {
+                        // Keep the share on record to be retried.
+                        *host
+                            .state_mut()
+                            .failed_payouts
+                            .entry(*member)
+                            .or_insert_with(Amount::zero) += amount;
+                    }
+                }
+                if to_finalizer && dust > Amount::zero() {
+                    host.invoke_transfer(&finalizer, dust).unwrap_abort();
+                }
+            }
+        }
+        EndOfLifePolicy::ToCreator => {
+            let creator = host.state().creator;
+            host.invoke_transfer(&creator, balance).unwrap_abort();
+        }
+        EndOfLifePolicy::Burn => {}
+    }
+}
+
+/// First step of finalizing a large club over several transactions:
+/// refunds the penalty deposits of a batch of members. The parameter is
+/// the position in the member list to start from, and at most
+/// `MAX_BATCH_SIZE` members are processed per call. Returns the position
+/// to continue from, or `None` once all deposits have been refunded and
+/// `finalizeComplete` can be called. The same accounts as for `finalize`
+/// can call this function.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * It fails to parse the parameter.
+/// * The Tanda club is already finalized.
+/// * The sender cannot finalize the club.
+#[receive(
+    contract = "dthrift",
+    name = "finalizeRefundBatch",
+    parameter = "u64",
+    return_value = "Option<u64>",
+    mutable,
+    error = "Error"
+)]
+fn finalize_refund_batch<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<Option<u64>, Error> {
+    ensure!(!host.state().finalized, Error::AlreadyFinalized);
+    ensure_can_finalize(ctx, host)?;
+    set_tanda_state(host.state_mut(), TandaState::Completed)?;
+
+    let cursor: u64 = ctx.parameter_cursor().get()?;
+    let next_cursor = refund_deposits(host, cursor);
+    if next_cursor.is_none() {
+        host.state_mut().refunds_complete = true;
+    }
+    record_activity(host.state_mut(), "finalizeRefundBatch", ctx.metadata().slot_time());
+    Ok(next_cursor)
+}
+
+/// Last step of finalizing a large club over several transactions: once
+/// all deposits have been refunded with `finalizeRefundBatch`, finalizes
+/// the club and handles the remaining balance like `finalize` does.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * The Tanda club is already finalized.
+/// * The sender cannot finalize the club.
+/// * Not all deposits have been refunded yet.
+#[receive(contract = "dthrift", name = "finalizeComplete", mutable, error = "Error")]
+fn finalize_complete<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<(), Error> {
+    ensure!(!host.state().finalized, Error::AlreadyFinalized);
+    ensure_can_finalize(ctx, host)?;
+    ensure!(host.state().refunds_complete, Error::RefundsPending);
+
+    host.state_mut().finalized = true;
+    release_collateral(host.state_mut());
+    distribute_remainder(host, ctx.invoker());
+    record_activity(host.state_mut(), "finalizeComplete", ctx.metadata().slot_time());
+    Ok(())
+}
+
+/// Lets a member leave the Tanda club. The member forfeits the right to
+/// receive future payouts, and their penalty deposit and collateral are
+/// sent to the configured forfeit destination: the pot, the remaining
+/// members, or the creator. Contributions already made stay in the pot. Before the
+/// payouts start, the first account on the waitlist takes the slot.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * The Tanda club is already finalized.
+/// * The sender is a contract.
+/// * The sender is not a member.
+#[receive(contract = "dthrift", name = "forfeit", mutable, error = "Error")]
+fn forfeit<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<(), Error> {
+    ensure!(!host.state().finalized, Error::AlreadyFinalized);
+
+    // Ensure that the sender is an account
+    let acc = match ctx.sender() {
+        Address::Account(acc) => acc,
+        Address::Contract(_) => return Err(Error::ContractMember),
+    };
+    ensure!(host.state().is_member(&acc), Error::NotJoined);
+
+    // A deposit sent to the creator must be covered by the balance.
+    if host.state().forfeit_destination == ForfeitDestination::Creator {
+        let deposit = host.state().deposits.get(&acc).copied().unwrap_or_else(Amount::zero);
+        let collateral = host.state().collaterals.get(&acc).copied().unwrap_or_else(Amount::zero);
+        ensure_balance_covers(host, deposit + collateral)?;
+    }
+
+    // Remove the member from the club.
+    let mut vacated_index = None;
+    if let Some(members) = host.state_mut().members.as_mut() {
+        vacated_index = members
+            .iter()
+            .find(|(address, _)| address == &acc)
+            .map(|(_, index)| *index);
+        members.retain(|(address, _)| address != &acc);
+    }
+    host.state_mut().contributors.remove(&acc);
+
+    let deposit = host
+        .state_mut()
+        .deposits
+        .remove(&acc)
+        .unwrap_or_else(Amount::zero);
+    let collateral = host
+        .state_mut()
+        .collaterals
+        .remove(&acc)
+        .unwrap_or_else(Amount::zero);
+    distribute_forfeited_deposit(host, deposit + collateral);
+
+    // Before the payouts start, the first waitlisted account takes the slot.
+    let tanda_state = host.state().tanda_state;
+    if tanda_state == TandaState::Open || tanda_state == TandaState::Closed {
+        if let Some(index) = vacated_index {
+            promote_from_waitlist(host.state_mut(), index, ctx.metadata().slot_time());
+        }
+    }
+    compact_members(host.state_mut());
+    record_activity(host.state_mut(), "forfeit", ctx.metadata().slot_time());
+    Ok(())
+}
+
+/// Promotes the first waitlisted account, if any, to a member with the
+/// given user index. The amount it paid becomes its deposit, apart from
+/// the join fee which goes to the pot and the collateral.
+fn promote_from_waitlist(state: &mut State, index: u64, now: Timestamp) {
+    if state.waitlist.is_empty() {
+        return;
+    }
+    let (account, amount) = state.waitlist.remove(0);
+    let join_fee = state.join_fee.min(amount);
+    let collateral = state.collateral_amount.min(amount - join_fee);
+    state.deposits.insert(account, amount - join_fee - collateral);
+    if collateral > Amount::zero() {
+        state.collaterals.insert(account, collateral);
+    }
+    state.total_contributions += join_fee;
+    state.joined_at.insert(account, now);
+    state.join_count += 1;
+    state.members.get_or_insert_with(Vec::new).push((account, index));
+}
+
+/// Joins the waitlist of a full club, paying the same amount as for
+/// joining. When a member leaves before the payouts start, the first
+/// account on the waitlist takes their slot. Until then the amount can be
+/// taken back with `leaveWaitlist`.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * The club is no longer in its contribution phase.
+/// * The sender is a contract.
+/// * The sender is already a member or on the waitlist.
+/// * The club is still open and has free slots.
+/// * The amount is not the amount required to join.
+#[receive(
+    contract = "dthrift",
+    name = "joinWaitlist",
+    mutable,
+    error = "Error",
+    payable
+)]
+fn join_waitlist<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+    amount: Amount,
+) -> Result<(), Error> {
+    let tanda_state = host.state().tanda_state;
+    ensure!(
+        tanda_state == TandaState::Open || tanda_state == TandaState::Closed,
+        Error::TandaClosed
+    );
+
+    // Ensure that the sender is an account
+    let acc = match ctx.sender() {
+        Address::Account(acc) => acc,
+        Address::Contract(_) => return Err(Error::ContractMember),
+    };
+    let state = host.state();
+    ensure!(
+        !state.is_member(&acc) && !state.waitlist.iter().any(|(address, _)| *address == acc),
+        Error::AlreadyJoined
+    );
+
+    let members = state.members.as_ref().map_or(0, |v| v.len()) as u64;
+    ensure!(
+        tanda_state == TandaState::Closed || members >= state.max_contributors,
+        Error::ClubNotFull
+    );
+    ensure!(
+        amount == state.required_join_amount(ctx.metadata().slot_time())?,
+        Error::InvalidPenaltyAmount
+    );
+
+    host.state_mut().waitlist.push((acc, amount));
+    record_activity(host.state_mut(), "joinWaitlist", ctx.metadata().slot_time());
+    Ok(())
+}
+
+/// Leaves the waitlist and transfers back the amount paid to join it.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * The sender is a contract.
+/// * The sender is not on the waitlist.
+#[receive(contract = "dthrift", name = "leaveWaitlist", mutable, error = "Error")]
+fn leave_waitlist<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<(), Error> {
+    // Ensure that the sender is an account
+    let acc = match ctx.sender() {
+        Address::Account(acc) => acc,
+        Address::Contract(_) => return Err(Error::ContractMember),
+    };
+    let position = host
+        .state()
+        .waitlist
+        .iter()
+        .position(|(address, _)| *address == acc)
+        .ok_or(Error::NotWaitlisted)?;
+    let amount = host.state().waitlist[position].1;
+    ensure_balance_covers(host, amount)?;
+
+    host.state_mut().waitlist.remove(position);
+    host.invoke_transfer(&acc, amount).unwrap_abort();
+    record_activity(host.state_mut(), "leaveWaitlist", ctx.metadata().slot_time());
+    Ok(())
+}
+
+/// Slashes part of a member's penalty deposit, for example after a missed
+/// contribution. The slashed amount is added to the pot. Only the creator
+/// can call this function.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * It fails to parse the parameter.
+/// * The caller is not the creator.
+/// * The account is not a member.
+/// * The amount exceeds the member's remaining deposit.
+#[receive(
+    contract = "dthrift",
+    name = "slash",
+    parameter = "SlashParameter",
+    mutable,
+    error = "Error"
+)]
+fn slash<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<(), Error> {
+    // Ensure that the caller is the owner of the contract
+    let owner = host.state().creator;
+    ensure!(
+        ctx.sender() == Address::Account(owner),
+        Error::NotAuthorized
+    );
+
+    let param: SlashParameter = ctx.parameter_cursor().get()?;
+    ensure!(host.state().is_member(&param.member), Error::NotJoined);
+
+    let state = host.state_mut();
+    let deposit = state
+        .deposits
+        .get_mut(&param.member)
+        .ok_or(Error::InsufficientBalance)?;
+    ensure!(param.amount <= *deposit, Error::InsufficientBalance);
+    *deposit -= param.amount;
+    state.total_contributions += param.amount;
+    record_activity(host.state_mut(), "slash", ctx.metadata().slot_time());
+    Ok(())
+}
+
+/// Transfers an amount to a member and returns whether it was transferred.
+/// A member whose account no longer exists is recorded as skipped instead
+/// of aborting the transaction, so the club does not get stuck.
+fn transfer_or_skip<S: HasStateApi>(
+    host: &mut impl HasHost<State, StateApiType = S>,
+    receiver: &AccountAddress,
+    amount: Amount,
+) -> bool {
+    match host.invoke_transfer(receiver, amount) {
+        Err(TransferError::MissingAccount) => {
+            if !host.state().skipped_receivers.contains(receiver) {
+                host.state_mut().skipped_receivers.push(*receiver);
+            }
+            false
+        }
+        result => {
+            result.unwrap_abort();
+            true
+        }
+    }
+}
+
+/// Sends a forfeited deposit to the configured forfeit destination.
+/// When shared among the remaining members, the indivisible remainder
+/// is added to the pot.
+fn distribute_forfeited_deposit<S: HasStateApi>(
+    host: &mut impl HasHost<State, StateApiType = S>,
+    deposit: Amount,
+) {
+    let destination = host.state().forfeit_destination;
+    match destination {
+        ForfeitDestination::Pot => host.state_mut().total_contributions += deposit,
+        ForfeitDestination::Members => {
+            let state = host.state_mut();
+            let members: Vec<AccountAddress> =
+                state.members.iter().flatten().map(|(address, _)| *address).collect();
+            let share = match equal_share(deposit, members.len() as u64) {
+                Some(share) => share,
+                None => {
+                    state.total_contributions += deposit;
+                    return;
+                }
+            };
+            let remainder = deposit - share * members.len() as u64;
+            for member in members {
+                *state.deposits.entry(member).or_insert_with(Amount::zero) += share;
+            }
+            state.total_contributions += remainder;
+        }
+        ForfeitDestination::Creator => {
+            let creator = host.state().creator;
+            host.invoke_transfer(&creator, deposit).unwrap_abort();
+        }
+    }
+}
+
+/// Refunds the remaining penalty deposits of a batch of members once the
+/// club is completed. The parameter is the position in the member list to
+/// start from, and at most `MAX_BATCH_SIZE` members are processed per call.
+/// Returns the position to continue from, or `None` when all members have
+/// been processed. Members whose account no longer exists are skipped and
+/// keep their deposit on record.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * It fails to parse the parameter.
+/// * The club is not completed yet.
+#[receive(
+    contract = "dthrift",
+    name = "refundBatch",
+    parameter = "u64",
+    return_value = "Option<u64>",
+    mutable,
+    error = "Error"
+)]
+fn refund_batch<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<Option<u64>, Error> {
+    ensure!(
+        host.state().tanda_state == TandaState::Completed,
+        Error::InvalidState
+    );
+
+    let cursor: u64 = ctx.parameter_cursor().get()?;
+    record_activity(host.state_mut(), "refundBatch", ctx.metadata().slot_time());
+    Ok(refund_deposits(host, cursor))
+}
+
+/// Refunds the deposits of at most `MAX_BATCH_SIZE` members, starting at
+/// the given position in the member list, and returns the position to
+/// continue from, if any.
+fn refund_deposits<S: HasStateApi>(
+    host: &mut impl HasHost<State, StateApiType = S>,
+    cursor: u64,
+) -> Option<u64> {
+    let members: Vec<AccountAddress> = host
+        .state()
+        .members
+        .iter()
+        .flatten()
+        .skip(cursor as usize)
+        .take(MAX_BATCH_SIZE as usize)
+        .map(|(address, _)| *address)
+        .collect();
+
+    for member in members.iter() {
+        if let Some(deposit) = host.state_mut().deposits.remove(member) {
+            if deposit > Amount::zero() && !transfer_or_skip(host, member, deposit) {
+                // Keep the deposit of a skipped member on record.
+                host.state_mut().deposits.insert(*member, deposit);
+            }
+        }
+    }
+
+    let next_cursor = cursor + members.len() as u64;
+    let member_count = host.state().members.as_ref().map_or(0, |v| v.len()) as u64;
+    if next_cursor < member_count {
+        Some(next_cursor)
+    } else {
+        None
+    }
+}
+
+/// Whether the user indices of the members are unique and increasing in
+/// join order, as `join_tanda` and `compact_members` keep them.
+fn validate_member_indices(members: &[(AccountAddress, u64)]) -> bool {
+    members.windows(2).all(|pair| pair[0].1 < pair[1].1)
+}
+
+/// Splits `total` into `parts` equal shares and returns one share, or
+/// `None` when there are no parts. The remainder of the division is not
+/// included in the shares; callers decide where it goes.
+fn equal_share(total: Amount, parts: u64) -> Option<Amount> {
+    total.micro_ccd.checked_div(parts).map(Amount::from_micro_ccd)
+}
+
+/// The share of `total` paid out in each of `cycles` cycles. The division
+/// truncates; the remainder stays in the pot and goes to the last payout.
+///
+/// # Errors
+///
+/// Returns `InvalidPayoutCycle` when there are no cycles to split over.
+fn per_cycle_share(total: Amount, cycles: u64) -> Result<Amount, Error> {
+    total
+        .micro_ccd
+        .checked_div(cycles)
+        .map(Amount::from_micro_ccd)
+        .ok_or(Error::InvalidPayoutCycle)
+}
+
+/// Checks that the penalty amount is compatible with the early bird
+/// deposit, which is a discount on it.
+fn validate_penalty_amount(
+    penalty_amount: Amount,
+    early_bird_count: u64,
+    early_bird_penalty: Amount,
+) -> Result<(), Error> {
+    ensure!(
+        early_bird_count == 0 || early_bird_penalty <= penalty_amount,
+        Error::InvalidPenaltyAmount
+    );
+    Ok(())
+}
+
+/// Checks that a club name is neither empty nor too long.
+fn validate_name(name: &str) -> Result<(), Error> {
+    ensure!(
+        !name.is_empty() && name.len() <= MAX_NAME_LENGTH,
+        Error::InvalidName
+    );
+    Ok(())
+}
+
+/// Closes the club for new members before the maximum number of members
+/// is reached. Members who already joined keep contributing as usual.
+/// Only the creator can call this function, while the club is open and
+/// once at least the minimum number of members has joined.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * The caller is not the creator.
+/// * The club is not open.
+/// * Fewer than the minimum number of members have joined.
+#[receive(contract = "dthrift", name = "closeJoining", mutable, error = "Error")]
+fn close_joining<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<(), Error> {
+    // Ensure that the caller is the owner of the contract
+    let owner = host.state().creator;
+    ensure!(
+        ctx.sender() == Address::Account(owner),
+        Error::NotAuthorized
+    );
+
+    ensure!(
+        host.state().tanda_state == TandaState::Open,
+        Error::TandaClosed
+    );
+
+    let member_count = host.state().members.as_ref().map_or(0, |v| v.len()) as u64;
+    ensure!(
+        member_count >= host.state().min_members,
+        Error::MinimumNotReached
+    );
+
+    set_tanda_state(host.state_mut(), TandaState::Closed)?;
+    record_activity(host.state_mut(), "closeJoining", ctx.metadata().slot_time());
+    Ok(())
+}
+
+/// Rebuilds the member list from the given roster of addresses and join
+/// indices, when the stored list was lost or no longer matches the
+/// contributors. Only the creator can call this function.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * The caller is not the creator.
+/// * The stored member list is consistent and needs no repair.
+/// * The roster includes an account that never joined.
+/// * The roster is not ordered by join index, has more entries than the
+///   maximum number of members, includes the null address or leaves out a
+///   contributor.
+#[receive(
+    contract = "dthrift",
+    name = "repairMembers",
+    parameter = "Vec<(AccountAddress, u64)>",
+    mutable,
+    error = "Error"
+)]
+fn repair_members<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<(), Error> {
+    // Ensure that the caller is the owner of the contract
+    let owner = host.state().creator;
+    ensure!(
+        ctx.sender() == Address::Account(owner),
+        Error::NotAuthorized
+    );
+
+    ensure!(!host.state().members_consistent(), Error::InvalidState);
+
+    let roster: Vec<(AccountAddress, u64)> = ctx.parameter_cursor().get()?;
+    ensure!(
+        roster.len() as u64 <= host.state().max_contributors,
+        Error::InvalidParameter
+    );
+    ensure!(
+        roster.iter().all(|(address, _)| *address != NULL_ACCOUNT),
+        Error::InvalidParameter
+    );
+    // Only accounts that actually joined can be put back on the list.
+    let state = host.state();
+    ensure!(
+        roster.iter().all(|(address, _)| {
+            state.joined_at.contains_key(address) || state.deposits.contains_key(address)
+        }),
+        Error::NotJoined
+    );
+    ensure!(
+        host.state().is_consistent_roster(&roster),
+        Error::InvalidParameter
+    );
+
+    let state = host.state_mut();
+    state.members = Some(roster);
+    record_activity(state, "repairMembers", ctx.metadata().slot_time());
+    Ok(())
+}
+
+/// Renames the club. Only the creator can call this function, while the
+/// club is open and, if the terms lock on the first contribution, before
+/// anybody contributed.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * The caller is not the creator.
+/// * The club is not open.
+/// * The terms are locked.
+/// * The name is empty or too long.
+#[receive(
+    contract = "dthrift",
+    name = "rename",
+    parameter = "String",
+    enable_logger,
+    mutable,
+    error = "Error"
+)]
+fn rename<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), Error> {
+    // Ensure that the caller is the owner of the contract
+    let owner = host.state().creator;
+    ensure!(
+        ctx.sender() == Address::Account(owner),
+        Error::NotAuthorized
+    );
+
+    ensure!(
+        host.state().tanda_state == TandaState::Open,
+        Error::TandaClosed
+    );
+    ensure!(!host.state().terms_locked, Error::AlreadyStarted);
+
+    let name: String = ctx.parameter_cursor().get()?;
+    validate_name(&name)?;
+
+    host.state_mut().name = name.clone();
+    logger.log(&Event::Renamed(RenamedEvent { name }))?;
+    record_activity(host.state_mut(), "rename", ctx.metadata().slot_time());
+    Ok(())
+}
+
+/// Changes the contribution amount. Like any setter of the club terms,
+/// this only works before the club starts: while it is open and nobody
+/// has contributed yet, so that no contribution was made against the old
+/// amount. Only the creator can call this function.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * It fails to parse the parameter.
+/// * The caller is not the creator.
+/// * The club is no longer open or has received contributions.
+/// * The amount is zero, or below the join fee.
+#[receive(
+    contract = "dthrift",
+    name = "setContributionAmount",
+    parameter = "Amount",
+    mutable,
+    error = "Error"
+)]
+fn set_contribution_amount<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<(), Error> {
+    // Ensure that the caller is the owner of the contract
+    let owner = host.state().creator;
+    ensure!(
+        ctx.sender() == Address::Account(owner),
+        Error::NotAuthorized
+    );
+
+    ensure!(
+        host.state().tanda_state == TandaState::Open && host.state().contributors.is_empty(),
+        Error::AlreadyStarted
+    );
+
+    let amount: Amount = ctx.parameter_cursor().get()?;
+    ensure!(amount > Amount::zero(), Error::InvalidContributionAmount);
+    ensure!(amount >= host.state().join_fee, Error::InvalidJoinFee);
+
+    host.state_mut().contribution_amount = amount;
+    record_activity(host.state_mut(), "setContributionAmount", ctx.metadata().slot_time());
+    Ok(())
+}
+
+/// Changes the penalty deposit members pay when joining. The deposits
+/// already paid are not adjusted, so this only works while the club is
+/// open and nobody has joined it or its waitlist yet. Only the creator can
+/// call this function.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * It fails to parse the parameter.
+/// * The caller is not the creator.
+/// * The club is no longer open or an account has joined.
+/// * The new amount is below the early bird deposit.
+#[receive(
+    contract = "dthrift",
+    name = "setPenaltyAmount",
+    parameter = "Amount",
+    mutable,
+    error = "Error"
+)]
+fn set_penalty_amount<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<(), Error> {
+    // Ensure that the caller is the owner of the contract
+    let owner = host.state().creator;
+    ensure!(
+        ctx.sender() == Address::Account(owner),
+        Error::NotAuthorized
+    );
+
+    let state = host.state();
+    let members = state.members.as_ref().map_or(0, |v| v.len());
+    ensure!(
+        state.tanda_state == TandaState::Open && members == 0 && state.waitlist.is_empty(),
+        Error::AlreadyStarted
+    );
+
+    let amount: Amount = ctx.parameter_cursor().get()?;
+    validate_penalty_amount(amount, state.early_bird_count, state.early_bird_penalty)?;
+    host.state_mut().penalty_amount = amount;
+    record_activity(host.state_mut(), "setPenaltyAmount", ctx.metadata().slot_time());
+    Ok(())
+}
+
+/// Attaches opaque metadata, such as a hashed contact handle, to the
+/// calling member for off-chain notifiers. Replaces any metadata set
+/// before. The metadata is not used by the contract itself.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * The caller is not a member.
+/// * The metadata is longer than `MAX_METADATA_LENGTH` bytes.
+#[receive(
+    contract = "dthrift",
+    name = "setMemberMetadata",
+    parameter = "Vec<u8>",
+    mutable,
+    error = "Error"
+)]
+fn set_member_metadata<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<(), Error> {
+    // Ensure that the sender is an account
+    let acc = match ctx.sender() {
+        Address::Account(acc) => acc,
+        Address::Contract(_) => return Err(Error::ContractMember),
+    };
+    ensure!(host.state().is_member(&acc), Error::NotJoined);
+
+    let metadata: Vec<u8> = ctx.parameter_cursor().get()?;
+    ensure!(
+        metadata.len() <= MAX_METADATA_LENGTH,
+        Error::MetadataTooLong
+    );
+
+    host.state_mut().member_metadata.insert(acc, metadata);
+    record_activity(host.state_mut(), "setMemberMetadata", ctx.metadata().slot_time());
+    Ok(())
+}
+
+/// Transfers the payouts credited to the caller to their account.
+/// Payouts are credited by `withdraw` and pulled by the member with this
+/// function, so that a failing transfer never affects the rotation.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * The sender is a contract.
+/// * The sender has nothing to claim.
+/// * The balance of the contract does not cover the claim.
+#[receive(contract = "dthrift", name = "claim", mutable, error = "Error")]
+fn claim_payout<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<(), Error> {
+    // Ensure that the sender is an account
+    let acc = match ctx.sender() {
+        Address::Account(acc) => acc,
+        Address::Contract(_) => return Err(Error::ContractMember),
+    };
+
+    let amount = host
+        .state()
+        .withdrawable_balances
+        .get(&acc)
+        .copied()
+        .ok_or(Error::NothingToClaim)?;
+    ensure_balance_covers(host, amount)?;
+
+    host.state_mut().withdrawable_balances.remove(&acc);
+    host.invoke_transfer(&acc, amount).unwrap_abort();
+    record_activity(host.state_mut(), "claim", ctx.metadata().slot_time());
+    Ok(())
+}
+
+/// Retries the transfer of a payout that failed when finalizing because
+/// the member's account was missing. Anyone can retry on behalf of the
+/// member. Returns whether the payout was transferred. After
+/// `MAX_PAYOUT_RETRIES` failed retries the payout is forfeited and handled
+/// like a forfeited deposit.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * It fails to parse the parameter.
+/// * The member has no failed payout.
+/// * The member is not a member of the club.
+#[receive(
+    contract = "dthrift",
+    name = "retryPayout",
+    parameter = "AccountAddress",
+    return_value = "bool",
+    mutable,
+    error = "Error"
+)]
+fn retry_payout<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<bool, Error> {
+    let member: AccountAddress = ctx.parameter_cursor().get()?;
+    let amount = host
+        .state()
+        .failed_payouts
+        .get(&member)
+        .copied()
+        .ok_or(Error::NothingToClaim)?;
+    ensure_payout_target(host.state(), &member)?;
+
+    record_activity(host.state_mut(), "retryPayout", ctx.metadata().slot_time());
+
+    match host.invoke_transfer(&member, amount) {
+        Err(TransferError::MissingAccount) => {
+            let state = host.state_mut();
+            let retries = state.payout_retries.entry(member).or_insert(0);
+            *retries += 1;
+            if *retries >= MAX_PAYOUT_RETRIES {
+                state.payout_retries.remove(&member);
+                state.failed_payouts.remove(&member);
+                distribute_forfeited_deposit(host, amount);
+            }
+            Ok(false)
+        }
+        result => {
+            result.unwrap_abort();
+            let state = host.state_mut();
+            state.failed_payouts.remove(&member);
+            state.payout_retries.remove(&member);
+            Ok(true)
+        }
+    }
+}
+
+/// Votes to abort the club. Once the configured supermajority of the
+/// members has voted, the club is aborted: it is finalized and every
+/// member is refunded, see `abort_club`. Voting opens once the abort vote
+/// delay has passed after the start time.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * The Tanda club is already finalized.
+/// * The abort vote delay has not passed yet.
+/// * The sender is a contract.
+/// * The sender is not a member.
+/// * The sender has already voted.
+#[receive(contract = "dthrift", name = "voteAbort", mutable, error = "Error")]
+fn vote_abort<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<(), Error> {
+    ensure!(!host.state().finalized, Error::AlreadyFinalized);
+
+    let voting_opens = host
+        .state()
+        .start_time
+        .checked_add(host.state().abort_vote_delay)
+        .ok_or(Error::InvalidState)?;
+    ensure!(ctx.metadata().slot_time() >= voting_opens, Error::NotStarted);
+
+    // Ensure that the sender is an account
+    let acc = match ctx.sender() {
+        Address::Account(acc) => acc,
+        Address::Contract(_) => return Err(Error::ContractMember),
+    };
+    ensure!(host.state().is_member(&acc), Error::NotJoined);
+    ensure!(
+        host.state_mut().abort_votes.insert(acc),
+        Error::AlreadyVoted
+    );
+
+    if host.state().abort_vote_count() >= host.state().abort_vote_threshold() {
+        abort_club(host);
+    }
+    record_activity(host.state_mut(), "voteAbort", ctx.metadata().slot_time());
+    Ok(())
+}
+
+/// Aborts the club and credits every member a refund, to be claimed with
+/// `claim`: their deposit and collateral, plus their contributions if they
+/// have not received a payout yet. Waitlisted accounts get back what they
+/// paid. When the balance left does not cover all refunds, they are reduced
+/// proportionally.
+fn abort_club<S: HasStateApi>(host: &mut impl HasHost<State, StateApiType = S>) {
+    let refunds = abort_refunds(host.state());
+    credit_refunds(host, refunds);
+
+    let state = host.state_mut();
+    state.deposits.clear();
+    state.collaterals.clear();
+    state.waitlist.clear();
+    state.aborted = true;
+    state.finalized = true;
+    set_tanda_state(state, TandaState::Completed).unwrap_abort();
+    state.next_receiver = None;
+}
+
+/// The refund every account is owed when the club is aborted: a member's
+/// deposit and collateral, plus their contributions if they have not
+/// received a payout yet, and the amount a waitlisted account paid.
+fn abort_refunds(state: &State) -> Vec<(AccountAddress, u64)> {
+    let contribution = state.contribution_amount.micro_ccd;
+    let mut refunds: Vec<(AccountAddress, u64)> = state
+        .members
+        .iter()
+        .flatten()
+        .map(|(address, _)| {
+            let deposit = state
+                .deposits
+                .get(address)
+                .map_or(0, |deposit| deposit.micro_ccd)
+                .saturating_add(
+                    state.collaterals.get(address).map_or(0, |collateral| collateral.micro_ccd),
+                );
+            let contributions = if state.withdrawn_addresses.contains(address) {
+                0
+            } else {
+                state
+                    .contributed_cycles
+                    .get(address)
+                    .map_or(0, |cycles| cycles.len() as u64)
+                    .saturating_mul(contribution)
+            };
+            (*address, deposit.saturating_add(contributions))
+        })
+        .collect();
+    refunds.extend(
+        state
+            .waitlist
+            .iter()
+            .map(|(address, amount)| (*address, amount.micro_ccd)),
+    );
+    refunds
+}
+
+/// Credits the refunds to the members, to be claimed with `claim`. When
+/// the balance left after the unclaimed payouts does not cover all
+/// refunds, they are reduced proportionally.
+fn credit_refunds<S: HasStateApi>(
+    host: &mut impl HasHost<State, StateApiType = S>,
+    refunds: Vec<(AccountAddress, u64)>,
+) {
+    let available = host
+        .self_balance()
+        .micro_ccd
+        .saturating_sub(host.state().unclaimed_total().micro_ccd);
+    let total: u128 = refunds.iter().map(|(_, refund)| *refund as u128).sum();
+
+    let state = host.state_mut();
+    for (address, refund) in refunds {
+        let refund = if total > available as u128 {
+            (refund as u128 * available as u128 / total) as u64
+        } else {
+            refund
+        };
+        if refund > 0 {
+            *state
+                .withdrawable_balances
+                .entry(address)
+                .or_insert_with(Amount::zero) += Amount::from_micro_ccd(refund);
+        }
+    }
+}
+
+/// Settles a club whose end time passed before all payout cycles were
+/// completed. Every member is credited their net position, to be claimed
+/// with `claim`: their deposit plus their contributions, minus the payouts
+/// they received. Accounts on the waitlist get back what they paid. The
+/// club is then finalized. Anyone can call this function.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * The Tanda club is already finalized.
+/// * The end time has not passed yet.
+/// * All payout cycles were completed; the club is finalized instead.
+#[receive(contract = "dthrift", name = "settleIncomplete", mutable, error = "Error")]
+fn settle_incomplete<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<(), Error> {
+    let state = host.state();
+    ensure!(!state.finalized, Error::AlreadyFinalized);
+    ensure!(
+        ctx.metadata().slot_time() > state.end_time,
+        Error::EndTimeNotReached
+    );
+    ensure!(
+        (state.completed_cycles.len() as u64) < state.payout_cycle,
+        Error::AlreadyFinished
+    );
+
+    let amount_of = |amounts: &BTreeMap<AccountAddress, Amount>, address: &AccountAddress| {
+        amounts.get(address).map_or(0, |amount| amount.micro_ccd)
+    };
+    let mut refunds: Vec<(AccountAddress, u64)> = state
+        .members
+        .iter()
+        .flatten()
+        .map(|(address, _)| {
+            let paid = amount_of(&state.deposits, address)
+                .saturating_add(amount_of(&state.collaterals, address))
+                .saturating_add(amount_of(&state.member_contributions, address));
+            let received = amount_of(&state.payouts_received, address);
+            (*address, paid.saturating_sub(received))
+        })
+        .collect();
+    refunds.extend(
+        state
+            .waitlist
+            .iter()
+            .map(|(address, amount)| (*address, amount.micro_ccd)),
+    );
+    credit_refunds(host, refunds);
+
+    let state = host.state_mut();
+    set_tanda_state(state, TandaState::Completed)?;
+    state.deposits.clear();
+    state.collaterals.clear();
+    state.waitlist.clear();
+    state.settled = true;
+    state.finalized = true;
+    state.next_receiver = None;
+    record_activity(host.state_mut(), "settleIncomplete", ctx.metadata().slot_time());
+    Ok(())
+}
+
+/// Lets the member next in line defer their payout: they move to the back
+/// of the rotation and the next eligible member is selected instead. A
+/// member can defer at most `MAX_DEFERRALS` times.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * The sender is a contract.
+/// * The sender is not the next receiver.
+/// * The sender has reached the deferral limit.
+#[receive(contract = "dthrift", name = "deferPayout", mutable, error = "Error")]
+fn defer_payout<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<(), Error> {
+    // Ensure that the sender is an account
+    let acc = match ctx.sender() {
+        Address::Account(acc) => acc,
+        Address::Contract(_) => return Err(Error::ContractMember),
+    };
+    ensure!(
+        host.state().next_receiver == Some(acc),
+        Error::NotAuthorized
+    );
+
+    let deferrals = host.state().deferrals.get(&acc).copied().unwrap_or(0);
+    ensure!(deferrals < MAX_DEFERRALS, Error::DeferralLimitReached);
+
+    let state = host.state_mut();
+    state.deferrals.insert(acc, deferrals + 1);
+
+    // Move the member to the back of the rotation, as if they joined last,
+    // keeping the list ordered by join index.
+    state.user_index += 1;
+    let new_index = state.user_index;
+    if let Some(members) = state.members.as_mut() {
+        if let Some(position) = members.iter().position(|(address, _)| *address == acc) {
+            members.remove(position);
+            members.push((acc, new_index));
+        }
+    }
+    if let Some(position) = state.frozen_order.iter().position(|address| *address == acc) {
+        state.frozen_order.remove(position);
+        state.frozen_order.push(acc);
+    }
+    if state.pending_withdrawal == Some(acc) {
+        state.pending_withdrawal = None;
+    }
+    reselect_receiver(state);
+    record_activity(host.state_mut(), "deferPayout", ctx.metadata().slot_time());
+    Ok(())
+}
+
+/// Lets a member who was left out of the rotation for not contributing
+/// catch up during the payout phase. The member pays the contributions for
+/// every cycle they did not pay for, plus the late penalty, and their slot
+/// is carried to the back of the rotation. They are paid out in a later
+/// cycle if any remain.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * The sender is a contract.
+/// * The sender's slot was not carried over.
+/// * The payout phase is not in progress.
+/// * The amount is not the outstanding contributions plus the penalty.
+#[receive(contract = "dthrift", name = "catchUp", mutable, payable, error = "Error")]
+fn catch_up<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+    amount: Amount,
+) -> Result<(), Error> {
+    // Ensure that the sender is an account
+    let acc = match ctx.sender() {
+        Address::Account(acc) => acc,
+        Address::Contract(_) => return Err(Error::ContractMember),
+    };
+    let state = host.state();
+    // Members who forfeited or were removed have no slot to get back.
+    ensure!(state.is_member(&acc), Error::NotJoined);
+    ensure!(state.carried_over.contains(&acc), Error::NotCarriedOver);
+    ensure!(
+        state.tanda_state == TandaState::Pending || state.tanda_state == TandaState::InProgress,
+        Error::InvalidState
+    );
+
+    let missed: Vec<u64> = (0..state.payout_cycle)
+        .filter(|cycle| !state.has_contributed(&acc, *cycle))
+        .collect();
+    let contributions = missed
+        .iter()
+        .try_fold(0u64, |total, cycle| {
+            total.checked_add(expected_contribution(state, *cycle).micro_ccd)
+        })
+        .ok_or(Error::InvalidState)?;
+    let penalty = state.late_penalty(&acc)?;
+    let owed = contributions
+        .checked_add(penalty.micro_ccd)
+        .ok_or(Error::InvalidState)?;
+    ensure!(amount.micro_ccd == owed, Error::InvalidContributionAmount);
+
+    let state = host.state_mut();
+    state
+        .contributed_cycles
+        .entry(acc)
+        .or_insert_with(BTreeSet::new)
+        .extend(missed);
+    state.contributors.insert(acc);
+    state.total_contributions += amount;
+    *state
+        .member_contributions
+        .entry(acc)
+        .or_insert_with(Amount::zero) += Amount::from_micro_ccd(contributions);
+    if penalty > Amount::zero() {
+        *state.penalties_paid.entry(acc).or_insert_with(Amount::zero) += penalty;
+        state.penalties_collected += penalty;
+    }
+
+    // Carry the slot to the back of the rotation.
+    state.carried_over.retain(|address| *address != acc);
+    state.skipped_receivers.retain(|address| *address != acc);
+    if !state.frozen_order.is_empty() {
+        state.frozen_order.retain(|address| *address != acc);
+        state.frozen_order.push(acc);
+    }
+    if state.next_receiver.is_none() {
+        reselect_receiver(state);
+    }
+    record_activity(host.state_mut(), "catchUp", ctx.metadata().slot_time());
+    Ok(())
+}
+
+/// Emits a `Reminder` event for the deadline of the current contribution
+/// cycle, for off-chain notifiers to relay to the members. Anyone can call
+/// this function within the reminder window before the deadline. Only one
+/// reminder is emitted per cycle; later calls in the same cycle do nothing.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * The club has not started yet.
+/// * The contribution cycles are over.
+/// * The current time is not within the reminder window.
+#[receive(
+    contract = "dthrift",
+    name = "emitReminder",
+    mutable,
+    enable_logger,
+    error = "Error"
+)]
+fn emit_reminder<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), Error> {
+    let now = ctx.metadata().slot_time();
+    ensure!(now >= host.state().start_time, Error::NotStarted);
+
+    let cycle = host.state().contribution_cycle(now);
+    ensure!(cycle < host.state().payout_cycle, Error::InvalidPayoutCycle);
+    if host.state().last_reminder_cycle == Some(cycle) {
+        return Ok(());
+    }
+
+    let deadline = host
+        .state()
+        .cycle_deadline(cycle)
+        .ok_or(Error::InvalidState)?;
+    let window_start = Timestamp::from_timestamp_millis(
+        deadline
+            .timestamp_millis()
+            .saturating_sub(host.state().reminder_window.millis()),
+    );
+    ensure!(now >= window_start, Error::OutsideReminderWindow);
+
+    host.state_mut().last_reminder_cycle = Some(cycle);
+    logger.log(&Event::Reminder(ReminderEvent { cycle, deadline }))?;
+    record_activity(host.state_mut(), "emitReminder", now);
+    Ok(())
+}
+
+/// Sets or clears the backup finalizer, an account that can finalize the
+/// club without being a member once the backup finalizer delay has passed
+/// after the end time. Only the creator can call this function.
+#[receive(
+    contract = "dthrift",
+    name = "setBackupFinalizer",
+    parameter = "Option<AccountAddress>",
+    mutable,
+    error = "Error"
+)]
+fn set_backup_finalizer<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<(), Error> {
+    // Ensure that the caller is the owner of the contract
+    let owner = host.state().creator;
+    ensure!(
+        ctx.sender() == Address::Account(owner),
+        Error::NotAuthorized
+    );
+
+    let backup_finalizer: Option<AccountAddress> = ctx.parameter_cursor().get()?;
+    host.state_mut().backup_finalizer = backup_finalizer;
+    record_activity(host.state_mut(), "setBackupFinalizer", ctx.metadata().slot_time());
+    Ok(())
+}
+
+/// Advances the club to the next cycle. The current cycle must have been
+/// paid out and the payment interval must have elapsed since that payout.
+/// Advancing past the last cycle completes the club. Cycles are also
+/// advanced automatically on the next withdrawal; this function allows the
+/// creator to do so manually. Only the creator can call this function.
+///
+/// # Errors
+///
+/// This function will return an error if:
+///
+/// * The caller is not the creator.
+/// * The club is already completed.
+/// * The current cycle has not been paid out.
+/// * The payment interval has not elapsed since the last payout.
+#[receive(contract = "dthrift", name = "advanceCycle", mutable, error = "Error")]
+fn advance_cycle_manually<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<(), Error> {
+    // Ensure that the caller is the owner of the contract
+    let owner = host.state().creator;
+    ensure!(
+        ctx.sender() == Address::Account(owner),
+        Error::NotAuthorized
+    );
+
+    advance_cycle(host.state_mut(), ctx.metadata().slot_time())?;
+    record_activity(host.state_mut(), "advanceCycle", ctx.metadata().slot_time());
+    Ok(())
+}
+
+// A function to Start a new contribution phase
+
+/// View function that returns the content of the state.
+#[receive(contract = "dthrift", name = "view", return_value = "State")]
+fn view<'b, S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &'b impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<&'b State> {
+    Ok(host.state())
+}
+
+/// View function that returns the amount an account has to send to join
+/// the club right now.
+#[receive(
+    contract = "dthrift",
+    name = "getRequiredJoinAmount",
+    return_value = "Amount",
+    error = "Error"
+)]
+fn get_required_join_amount<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> Result<Amount, Error> {
+    host.state()
+        .required_join_amount(ctx.metadata().slot_time())
+}
+
+/// View function that returns the amount paid out by the most recent
+/// withdrawal.
+#[receive(contract = "dthrift", name = "getLastPayout", return_value = "Amount")]
+fn get_last_payout<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Amount> {
+    Ok(host.state().last_payout_amount)
+}
+
+/// View function that exports a versioned snapshot of the critical state
+/// of the club for off-chain backup.
+#[receive(contract = "dthrift", name = "exportSnapshot", return_value = "Snapshot")]
+fn export_snapshot<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Snapshot> {
+    Ok(snapshot(host.state()))
+}
+
+/// View function that returns the SHA-256 hash of the serialized snapshot
+/// returned by `exportSnapshot`, so members can check an off-chain copy
+/// against the contract.
+#[receive(
+    contract = "dthrift",
+    name = "getStateHash",
+    return_value = "[u8; 32]",
+    crypto_primitives
+)]
+fn get_state_hash<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ReceiveResult<[u8; 32]> {
+    let bytes = to_bytes(&snapshot(host.state()));
+    Ok(crypto_primitives.hash_sha2_256(&bytes).0)
+}
+
+/// The versioned snapshot of the critical state of the club.
+fn snapshot(state: &State) -> Snapshot {
+    Snapshot {
+        version: SNAPSHOT_VERSION,
+        name: state.name.clone(),
+        description: state.description.clone(),
+        creator: state.creator,
+        contribution_amount: state.contribution_amount,
+        penalty_amount: state.penalty_amount,
+        payout_cycle: state.payout_cycle,
+        start_time: state.start_time,
+        end_time: state.end_time,
+        time_interval: state.time_interval,
+        max_contributors: state.max_contributors,
+        tanda_state: state.tanda_state,
+        members: state.members.clone().unwrap_or_default(),
+        contributors: state.contributors.clone(),
+        withdrawn_addresses: state.withdrawn_addresses.clone(),
+        current_cycle: state.current_cycle,
+        completed_cycles: state.completed_cycles.clone(),
+        total_contributions: state.total_contributions,
+    }
+}
+
+/// View function that returns the part of the caller's penalty deposit
+/// that is still refundable, i.e. net of any slashing.
+#[receive(
+    contract = "dthrift",
+    name = "getMyRefundableDeposit",
+    return_value = "Amount"
+)]
+fn get_my_refundable_deposit<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Amount> {
+    let deposit = host.state().deposits.get(&ctx.invoker()).copied();
+    Ok(deposit.unwrap_or_else(Amount::zero))
+}
+
+/// View function that returns the total that would be refunded if the club
+/// were aborted now: every member's deposit and collateral, plus their
+/// contributions if they have not received a payout yet, and the payments
+/// of waitlisted accounts. Like the refunds themselves, it is bounded by
+/// the balance left after the unclaimed payouts.
+#[receive(
+    contract = "dthrift",
+    name = "getTotalRefundable",
+    return_value = "Amount"
+)]
+fn get_total_refundable<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Amount> {
+    let state = host.state();
+    let total: u64 = abort_refunds(state)
+        .iter()
+        .fold(0u64, |total, (_, refund)| total.saturating_add(*refund));
+    let available = host
+        .self_balance()
+        .micro_ccd
+        .saturating_sub(state.unclaimed_total().micro_ccd);
+    Ok(Amount::from_micro_ccd(total.min(available)))
+}
+
+/// View function that returns the status of the given member.
+#[receive(
+    contract = "dthrift",
+    name = "getMemberStatus",
+    parameter = "AccountAddress",
+    return_value = "MemberStatus",
+    error = "Error"
+)]
+fn get_member_status<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> Result<MemberStatus, Error> {
+    let member: AccountAddress = ctx.parameter_cursor().get()?;
+    member_status(host.state(), &member, ctx.metadata().slot_time())
+}
+
+/// View function that returns the statuses of up to `MAX_PAGE_SIZE`
+/// members at once, in the order of the given addresses.
+#[receive(
+    contract = "dthrift",
+    name = "getMemberStatuses",
+    parameter = "Vec<AccountAddress>",
+    return_value = "Vec<MemberStatus>",
+    error = "Error"
+)]
+fn get_member_statuses<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> Result<Vec<MemberStatus>, Error> {
+    let members: Vec<AccountAddress> = ctx.parameter_cursor().get()?;
+    ensure!(members.len() as u64 <= MAX_PAGE_SIZE, Error::TooManyEntries);
+    let now = ctx.metadata().slot_time();
+    members
+        .iter()
+        .map(|member| member_status(host.state(), member, now))
+        .collect()
+}
+
+/// The status of the given member at `now`.
+fn member_status(state: &State, member: &AccountAddress, now: Timestamp) -> Result<MemberStatus, Error> {
+    let member = *member;
+    let index = state
+        .members
+        .as_ref()
+        .and_then(|members| members.iter().find(|(address, _)| *address == member))
+        .map(|(_, index)| *index)
+        .ok_or(Error::NotJoined)?;
+    let cycle = state.contribution_cycle(now);
+    Ok(MemberStatus {
+        index,
+        deposit: state.deposits.get(&member).copied().unwrap_or_else(Amount::zero),
+        contributed_this_cycle: state.has_contributed(&member, cycle),
+        withdrawn: state.withdrawn_addresses.contains(&member),
+        metadata: state.member_metadata.get(&member).cloned().unwrap_or_default(),
+    })
+}
+
+/// View function that returns the ledger of the given member.
+#[receive(
+    contract = "dthrift",
+    name = "getMemberLedger",
+    parameter = "AccountAddress",
+    return_value = "MemberLedger",
+    error = "Error"
+)]
+fn get_member_ledger<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> Result<MemberLedger, Error> {
+    let member: AccountAddress = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    ensure!(state.is_member(&member), Error::NotJoined);
+
+    let amount_of = |amounts: &BTreeMap<AccountAddress, Amount>| {
+        amounts.get(&member).copied().unwrap_or_else(Amount::zero)
+    };
+    let cycle = state.member_cycle(&member, ctx.metadata().slot_time());
+    let penalties_owed = if !state.has_contributed(&member, cycle) && state.is_late(&member, cycle) {
+        state.late_penalty(&member)?
+    } else {
+        Amount::zero()
+    };
+    Ok(MemberLedger {
+        contributed: amount_of(&state.member_contributions),
+        penalties_owed,
+        penalties_paid: amount_of(&state.penalties_paid),
+        deposit: amount_of(&state.deposits),
+        withdrawn: amount_of(&state.payouts_received),
+    })
+}
+
+/// View function that returns the realized return of the given member in
+/// micro CCD: what they received, less what they contributed and the
+/// penalties they paid. Deposits and join fees are not counted.
+#[receive(
+    contract = "dthrift",
+    name = "getMemberReturn",
+    parameter = "AccountAddress",
+    return_value = "i128",
+    error = "Error"
+)]
+fn get_member_return<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> Result<i128, Error> {
+    let member: AccountAddress = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    ensure!(state.is_member(&member), Error::NotJoined);
+
+    let micro_ccd_of = |amounts: &BTreeMap<AccountAddress, Amount>| {
+        i128::from(amounts.get(&member).map_or(0, |amount| amount.micro_ccd))
+    };
+    Ok(micro_ccd_of(&state.payouts_received)
+        - micro_ccd_of(&state.member_contributions)
+        - micro_ccd_of(&state.penalties_paid))
+}
+
+/// View function that returns a page of the payout history, in the order
+/// the payouts were made.
+#[receive(
+    contract = "dthrift",
+    name = "getPayoutLedger",
+    parameter = "PaginationParams",
+    return_value = "PayoutLedgerPage"
+)]
+fn get_payout_ledger<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<PayoutLedgerPage> {
+    let params: PaginationParams = ctx.parameter_cursor().get()?;
+    let history = &host.state().payout_history;
+    let limit = params.limit.min(MAX_PAGE_SIZE);
+    let rows: Vec<PayoutRecord> = history
+        .iter()
+        .skip(params.offset as usize)
+        .take(limit as usize)
+        .cloned()
+        .collect();
+    let end = params.offset.saturating_add(rows.len() as u64);
+    let next_offset = if end < history.len() as u64 && !rows.is_empty() {
+        Some(end)
+    } else {
+        None
+    };
+    Ok(PayoutLedgerPage { rows, next_offset })
+}
+
+/// View function that returns the members who have paid for cycles after
+/// the current contribution cycle, with the number of such cycles.
+#[receive(
+    contract = "dthrift",
+    name = "getPrepaidMembers",
+    return_value = "Vec<(AccountAddress, u64)>"
+)]
+fn get_prepaid_members<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Vec<(AccountAddress, u64)>> {
+    let state = host.state();
+    let cycle = state.contribution_cycle(ctx.metadata().slot_time());
+    let prepaid_members = state
+        .contributed_cycles
+        .iter()
+        .map(|(member, cycles)| (*member, cycles.range(cycle + 1..).count() as u64))
+        .filter(|(_, prepaid_cycles)| *prepaid_cycles > 0)
+        .collect();
+    Ok(prepaid_members)
+}
+
+/// View function that returns the complete rotation order, i.e. the order
+/// in which members receive their payout.
+#[receive(
+    contract = "dthrift",
+    name = "getRotationOrder",
+    return_value = "Vec<AccountAddress>"
+)]
+fn get_rotation_order<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Vec<AccountAddress>> {
+    Ok(rotation_order(host.state()))
+}
+
+/// View function that returns the zero-based position of the given account
+/// in the rotation order, or `None` if it is not in the rotation, for
+/// example because it left the club or never joined.
+#[receive(
+    contract = "dthrift",
+    name = "getRotationPosition",
+    parameter = "AccountAddress",
+    return_value = "Option<u64>"
+)]
+fn get_rotation_position<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Option<u64>> {
+    let account: AccountAddress = ctx.parameter_cursor().get()?;
+    Ok(rotation_order(host.state())
+        .iter()
+        .position(|address| *address == account)
+        .map(|position| position as u64))
+}
+
+/// View function that returns the deadline of the current contribution
+/// cycle of the given member, which depends on the deadline mode.
+#[receive(
+    contract = "dthrift",
+    name = "getMemberDeadline",
+    parameter = "AccountAddress",
+    return_value = "Option<Timestamp>"
+)]
+fn get_member_deadline<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Option<Timestamp>> {
+    let member: AccountAddress = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    let cycle = state.member_cycle(&member, ctx.metadata().slot_time());
+    Ok(state.member_cycle_deadline(&member, cycle))
+}
+
+/// View function that returns how much the pot falls short of a full
+/// payout for the current cycle: one contribution from every member,
+/// capped at the maximum payout per cycle. Zero when the cycle is funded.
+#[receive(contract = "dthrift", name = "getCycleShortfall", return_value = "Amount")]
+fn get_cycle_shortfall<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Amount> {
+    let state = host.state();
+    let members = state.members.as_ref().map_or(0, |v| v.len()) as u64;
+    let contribution = expected_contribution(state, state.current_cycle);
+    let mut required = contribution.micro_ccd.saturating_mul(members);
+    if let Some(cap) = state.max_payout_per_cycle {
+        required = required.min(cap.micro_ccd);
+    }
+    Ok(Amount::from_micro_ccd(
+        required.saturating_sub(state.distributable_pot().micro_ccd),
+    ))
+}
+
+/// View function that returns when the contribution phase opens and
+/// closes: from the start time until all payout cycles have passed, which
+/// is also when the payouts can start.
+#[receive(
+    contract = "dthrift",
+    name = "getContributionPhase",
+    return_value = "(Timestamp, Timestamp)"
+)]
+fn get_contribution_phase<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<(Timestamp, Timestamp)> {
+    let state = host.state();
+    Ok((state.start_time, state.withdrawal_start_time))
+}
+
+/// View function that returns when the caller last contributed, or `None`
+/// if they never did.
+#[receive(
+    contract = "dthrift",
+    name = "getMyLastContributionTime",
+    return_value = "Option<Timestamp>"
+)]
+fn get_my_last_contribution_time<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Option<Timestamp>> {
+    Ok(host.state().last_contribution_times.get(&ctx.invoker()).copied())
+}
+
+/// View function that returns the payouts credited to the caller that they
+/// can pull with `claim`, or zero if there is nothing to claim.
+#[receive(contract = "dthrift", name = "getMyClaimable", return_value = "Amount")]
+fn get_my_claimable<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Amount> {
+    Ok(host
+        .state()
+        .withdrawable_balances
+        .get(&ctx.invoker())
+        .copied()
+        .unwrap_or_else(Amount::zero))
+}
+
+/// View function that returns the past contribution cycles the caller has
+/// not paid for, in order. The current cycle is not included.
+#[receive(
+    contract = "dthrift",
+    name = "getMyOutstandingCycles",
+    return_value = "Vec<u64>",
+    error = "Error"
+)]
+fn get_my_outstanding_cycles<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> Result<Vec<u64>, Error> {
+    let member = ctx.invoker();
+    let state = host.state();
+    ensure!(state.is_member(&member), Error::NotJoined);
+
+    let current = state
+        .member_cycle(&member, ctx.metadata().slot_time())
+        .min(state.payout_cycle);
+    Ok((0..current)
+        .filter(|cycle| !state.has_contributed(&member, *cycle))
+        .collect())
+}
+
+/// View function that returns the deadline of the caller's next unpaid
+/// contribution cycle, skipping cycles already prepaid and following the
+/// deadline mode.
+#[receive(
+    contract = "dthrift",
+    name = "getMyNextDeadline",
+    return_value = "Timestamp",
+    error = "Error"
+)]
+fn get_my_next_deadline<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> Result<Timestamp, Error> {
+    let member = ctx.invoker();
+    let state = host.state();
+    ensure!(state.is_member(&member), Error::NotJoined);
+
+    let current = state.member_cycle(&member, ctx.metadata().slot_time());
+    let cycle = (current..state.payout_cycle)
+        .find(|cycle| !state.has_contributed(&member, *cycle))
+        .ok_or(Error::AlreadyFinished)?;
+    state
+        .member_cycle_deadline(&member, cycle)
+        .ok_or(Error::InvalidState)
+}
+
+/// View function that returns whether the club currently takes
+/// contributions.
+#[receive(
+    contract = "dthrift",
+    name = "isAcceptingContributions",
+    return_value = "bool"
+)]
+fn is_accepting_contributions<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<bool> {
+    Ok(host.state().accepts_contributions(ctx.metadata().slot_time()))
+}
+
+/// View function that returns the total amount finalizing the club would
+/// pay out of the remaining balance under the end of life policy.
+#[receive(
+    contract = "dthrift",
+    name = "previewFinalizeRemainder",
+    return_value = "Amount"
+)]
+fn preview_finalize_remainder<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Amount> {
+    Ok(remainder_payout(host))
+}
+
+/// View function that returns which optional features are enabled.
+#[receive(
+    contract = "dthrift",
+    name = "getCapabilities",
+    return_value = "Capabilities"
+)]
+fn get_capabilities<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Capabilities> {
+    let state = host.state();
+    Ok(Capabilities {
+        prepay: state.allow_prepay,
+        cosign: state.cosign_threshold.is_some(),
+        join_fee: state.join_fee > Amount::zero(),
+        late_join_multiplier: state.late_join_multiplier > 1,
+        sub_rounds: state.sub_round_size.is_some(),
+        payout_cap: state.max_payout_per_cycle.is_some(),
+        backup_finalizer: state.backup_finalizer.is_some(),
+        reminders: state.reminder_window.millis() > 0,
+    })
+}
+
+/// The optional settings of the club that differ from their defaults, with
+/// their values. The defaults leave every optional feature disabled, as
+/// `init-param.json` does.
+fn non_default_config(state: &State) -> Vec<ConfigField> {
+    let defaults = [
+        (
+            ConfigField::BackupFinalizerDelay(state.backup_finalizer_delay),
+            state.backup_finalizer_delay == DEFAULT_BACKUP_FINALIZER_DELAY,
+        ),
+        (
+            ConfigField::LateJoinMultiplier(state.late_join_multiplier),
+            state.late_join_multiplier <= 1,
+        ),
+        (
+            ConfigField::ForfeitDestination(state.forfeit_destination),
+            state.forfeit_destination == ForfeitDestination::Pot,
+        ),
+        (
+            ConfigField::Decimals(state.decimals),
+            state.decimals == CCD_DECIMALS,
+        ),
+        (
+            ConfigField::AllowPrepay(state.allow_prepay),
+            !state.allow_prepay,
+        ),
+        (
+            ConfigField::MinMembers(state.min_members),
+            state.min_members == DEFAULT_MIN_MEMBERS,
+        ),
+        (
+            ConfigField::EndOfLife(state.end_of_life),
+            state.end_of_life == EndOfLifePolicy::DistributeEqually,
+        ),
+        (
+            ConfigField::ReminderWindow(state.reminder_window),
+            state.reminder_window == DEFAULT_REMINDER_WINDOW,
+        ),
+        (
+            ConfigField::CosignThreshold(state.cosign_threshold),
+            state.cosign_threshold.is_none(),
+        ),
+        (
+            ConfigField::JoinFee(state.join_fee),
+            state.join_fee == Amount::zero(),
+        ),
+        (
+            ConfigField::SubRoundSize(state.sub_round_size),
+            state.sub_round_size.is_none(),
+        ),
+        (
+            ConfigField::AbortSupermajorityBps(state.abort_supermajority_bps),
+            state.abort_supermajority_bps == DEFAULT_ABORT_SUPERMAJORITY_BPS,
+        ),
+        (
+            ConfigField::MaxPayoutPerCycle(state.max_payout_per_cycle),
+            state.max_payout_per_cycle.is_none(),
+        ),
+        (
+            ConfigField::DeadlineMode(state.deadline_mode),
+            state.deadline_mode == DeadlineMode::Global,
+        ),
+        (
+            ConfigField::MinStreak(state.min_streak),
+            state.min_streak == 0,
+        ),
+        (
+            ConfigField::PayoutQuorumBps(state.payout_quorum_bps),
+            state.payout_quorum_bps == BPS_DENOMINATOR,
+        ),
+        (
+            ConfigField::ProbationCycles(state.probation_cycles),
+            state.probation_cycles == 0,
+        ),
+        (
+            ConfigField::EscalationBps(state.escalation_bps),
+            state.escalation_bps == 0,
+        ),
+        (ConfigField::LockTerms(state.lock_terms), !state.lock_terms),
+        (
+            ConfigField::FinalCycleTolerance(state.final_cycle_tolerance),
+            state.final_cycle_tolerance == Amount::zero(),
+        ),
+        (
+            ConfigField::DustPolicy(state.dust_policy),
+            state.dust_policy == DustPolicy::Members,
+        ),
+        (
+            ConfigField::AutoPenalize(state.auto_penalize),
+            !state.auto_penalize,
+        ),
+        (
+            ConfigField::AbortVoteDelay(state.abort_vote_delay),
+            state.abort_vote_delay.millis() == 0,
+        ),
+        (
+            ConfigField::StrictFinalize(state.strict_finalize),
+            !state.strict_finalize,
+        ),
+        (
+            ConfigField::MinJoinGap(state.min_join_gap),
+            state.min_join_gap.millis() == 0,
+        ),
+        (
+            ConfigField::EarlyBirdCount(state.early_bird_count),
+            state.early_bird_count == 0,
+        ),
+        (
+            ConfigField::EarlyBirdPenalty(state.early_bird_penalty),
+            state.early_bird_penalty == Amount::zero(),
+        ),
+        (
+            ConfigField::CollateralAmount(state.collateral_amount),
+            state.collateral_amount == Amount::zero(),
+        ),
+        (
+            ConfigField::MinPotForPayout(state.min_pot_for_payout),
+            state.min_pot_for_payout == Amount::zero(),
+        ),
+        (
+            ConfigField::SoftCloseBps(state.soft_close_bps),
+            state.soft_close_bps.is_none(),
+        ),
+        (
+            ConfigField::ContributionCooldownDivisor(state.contribution_cooldown_divisor),
+            state.contribution_cooldown_divisor == DEFAULT_CONTRIBUTION_COOLDOWN_DIVISOR,
+        ),
+    ];
+    defaults
+        .iter()
+        .filter(|(_, is_default)| !is_default)
+        .map(|(field, _)| *field)
+        .collect()
+}
+
+/// View function that returns the optional settings of the club that
+/// differ from their defaults, to spot unusual clubs.
+#[receive(
+    contract = "dthrift",
+    name = "getNonDefaultConfig",
+    return_value = "Vec<ConfigField>"
+)]
+fn get_non_default_config<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Vec<ConfigField>> {
+    Ok(non_default_config(host.state()))
+}
+
+/// View function that returns whether the balance of the contract matches
+/// its accounting: the undistributed pot, the deposits not refunded yet
+/// and the payouts not claimed yet, up to one micro CCD of dust per member
+/// left by equal splits. Once finalized, the pot has been handled and the
+/// balance only has to cover the deposits and unclaimed payouts.
+#[receive(contract = "dthrift", name = "isReconciled", return_value = "bool")]
+fn is_reconciled<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<bool> {
+    let state = host.state();
+    let balance = host.self_balance().micro_ccd;
+    let reserved = state.deposits_total().micro_ccd + state.unclaimed_total().micro_ccd;
+    if state.finalized {
+        return Ok(balance >= reserved);
+    }
+    let expected = reserved + state.distributable_pot().micro_ccd;
+    let dust = state.members.as_ref().map_or(0, |v| v.len()) as u64;
+    Ok(balance >= expected && balance - expected <= dust)
+}
+
+/// View function that returns whether the club can be finalized now, or
+/// why not. Whether the caller is allowed to finalize is not checked.
+#[receive(
+    contract = "dthrift",
+    name = "canFinalize",
+    return_value = "FinalizeEligibility"
+)]
+fn can_finalize<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<FinalizeEligibility> {
+    let state = host.state();
+    if state.finalized {
+        return Ok(FinalizeEligibility::AlreadyFinalized);
+    }
+    let now = ctx.metadata().slot_time();
+    if !state.cycles_complete(now) || !state.end_time_allows_finalize(now) {
+        return Ok(FinalizeEligibility::NotStarted);
+    }
+    Ok(FinalizeEligibility::Ok)
+}
+
+/// View function that returns the actions the given account can take on
+/// the club right now, so front ends do not have to repeat the checks of
+/// each entrypoint.
+#[receive(
+    contract = "dthrift",
+    name = "getAvailableActions",
+    parameter = "AccountAddress",
+    return_value = "Vec<ActionKind>"
+)]
+fn get_available_actions<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Vec<ActionKind>> {
+    let account: AccountAddress = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    let now = ctx.metadata().slot_time();
+    let member = state.is_member(&account);
+    let mut actions = Vec::new();
+
+    let members = state.members.as_ref().map_or(0, |v| v.len()) as u64;
+    if !member
+        && state.tanda_state == TandaState::Open
+        && state.start_time <= now
+        && now < state.end_time
+        && members < state.max_contributors
+    {
+        actions.push(ActionKind::Join);
+    }
+
+    if member
+        && state.accepts_contributions(now)
+        && !state.has_contributed(&account, state.member_cycle(&account, now))
+    {
+        actions.push(ActionKind::Contribute);
+    }
+
+    if account == state.creator
+        && !state.withdrawal_phase_started
+        && (state.tanda_state == TandaState::Open || state.tanda_state == TandaState::Closed)
+        && now >= state.withdrawal_start_time
+        && state.has_payout_quorum()
+    {
+        actions.push(ActionKind::StartPayouts);
+    }
+
+    if ensure_can_withdraw(state, &account, now).is_ok() {
+        actions.push(ActionKind::Withdraw);
+    }
+
+    if state.withdrawable_balances.contains_key(&account) {
+        actions.push(ActionKind::Claim);
+    }
+
+    if !state.finalized {
+        let finalizer = member
+            || (state.backup_finalizer == Some(account)
+                && state
+                    .end_time
+                    .checked_add(state.backup_finalizer_delay)
+                    .map_or(false, |earliest_time| now >= earliest_time));
+        if state.cycles_complete(now) && state.end_time_allows_finalize(now) && finalizer {
+            actions.push(ActionKind::Finalize);
+        }
+        if now > state.end_time && (state.completed_cycles.len() as u64) < state.payout_cycle {
+            actions.push(ActionKind::SettleIncomplete);
+        }
+    }
+    Ok(actions)
+}
+
+/// View function that returns the fees and penalties of the club. There
+/// is no creator fee.
+#[receive(contract = "dthrift", name = "getFeeSchedule", return_value = "FeeSchedule")]
+fn get_fee_schedule<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<FeeSchedule> {
+    let state = host.state();
+    Ok(FeeSchedule {
+        join_fee: state.join_fee,
+        penalty_amount: state.penalty_amount,
+        late_join_multiplier: state.late_join_multiplier,
+        escalation_bps: state.escalation_bps,
+        auto_penalize: state.auto_penalize,
+    })
+}
+
+/// View function that returns the activity counters of the club for
+/// monitoring.
+#[receive(contract = "dthrift", name = "getMetrics", return_value = "Metrics")]
+fn get_metrics<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Metrics> {
+    let state = host.state();
+    Ok(Metrics {
+        joins: state.join_count,
+        contributions: state.contribution_count,
+        payouts: state.payout_count,
+        penalties_collected: state.penalties_collected,
+        balance: host.self_balance(),
+    })
+}
+
+/// View function that returns the status of the vote to abort the club.
+#[receive(
+    contract = "dthrift",
+    name = "getAbortVoteStatus",
+    return_value = "VoteStatus"
+)]
+fn get_abort_vote_status<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<VoteStatus> {
+    let state = host.state();
+    Ok(VoteStatus {
+        votes: state.abort_vote_count(),
+        threshold: state.abort_vote_threshold(),
+        passed: state.aborted,
+    })
+}
+
+/// View function that returns the time elapsed since the club was created.
+#[receive(contract = "dthrift", name = "getAge", return_value = "Duration")]
+fn get_age<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Duration> {
+    let age = ctx
+        .metadata()
+        .slot_time()
+        .duration_since(host.state().created_at)
+        .unwrap_or_else(|| Duration::from_millis(0));
+    Ok(age)
+}
+
+/// View function that returns the time left until joining closes at the
+/// end time, or `None` if joining is already closed.
+#[receive(
+    contract = "dthrift",
+    name = "getJoinDeadlineEta",
+    return_value = "Option<Duration>"
+)]
+fn get_join_deadline_eta<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Option<Duration>> {
+    let state = host.state();
+    let now = ctx.metadata().slot_time();
+    if state.tanda_state != TandaState::Open || now >= state.end_time {
+        return Ok(None);
+    }
+    Ok(state.end_time.duration_since(now))
+}
+
+/// View function that returns who initialized the instance and when. The
+/// init origin is the creator, which never changes.
+#[receive(contract = "dthrift", name = "getProvenance", return_value = "Provenance")]
+fn get_provenance<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Provenance> {
+    Ok(Provenance {
+        init_origin: host.state().creator,
+        initialized_at: host.state().created_at,
+    })
+}
+
+/// View function that returns the sub-round currently being paid out.
+/// Always 0 when the rotation is not split into sub-rounds.
+#[receive(contract = "dthrift", name = "getSubRound", return_value = "u64")]
+fn get_sub_round<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<u64> {
+    Ok(host.state().sub_round)
+}
+
+/// View function that returns each member with the payout cycle they
+/// received or are scheduled to receive their payout in, following the
+/// rotation order.
+#[receive(
+    contract = "dthrift",
+    name = "getSlotAssignments",
+    return_value = "Vec<(AccountAddress, u64)>"
+)]
+fn get_slot_assignments<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Vec<(AccountAddress, u64)>> {
+    let state = host.state();
+    let mut assignments: Vec<(AccountAddress, u64)> = state
+        .completed_cycles
+        .iter()
+        .flat_map(|(cycle, receivers)| receivers.iter().map(move |receiver| (*receiver, *cycle)))
+        .collect();
+    let mut next_cycle = if state.is_cycle_paid(state.current_cycle) {
+        state.current_cycle + 1
+    } else {
+        state.current_cycle
+    };
+    for member in rotation_order(state).into_iter().skip(assignments.len()) {
+        assignments.push((member, next_cycle));
+        next_cycle += 1;
+    }
+    Ok(assignments)
+}
+
+/// View function that returns whether every member has paid for the
+/// current contribution cycle. Returns false for a club without members.
+#[receive(
+    contract = "dthrift",
+    name = "allContributedThisCycle",
+    return_value = "bool"
+)]
+fn all_contributed_this_cycle<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<bool> {
+    let state = host.state();
+    let cycle = state.contribution_cycle(ctx.metadata().slot_time());
+    let members = state.members.as_ref().filter(|members| !members.is_empty());
+    Ok(members.map_or(false, |members| {
+        members
+            .iter()
+            .all(|(address, _)| state.has_contributed(address, cycle))
+    }))
+}
+
+/// View function that returns whether a member contributed for the given
+/// contribution cycle. Returns false for cycles that have not been paid for.
+#[receive(
+    contract = "dthrift",
+    name = "didContribute",
+    parameter = "(AccountAddress, u64)",
+    return_value = "bool"
+)]
+fn did_contribute<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<bool> {
+    let (member, cycle): (AccountAddress, u64) = ctx.parameter_cursor().get()?;
+    Ok(host.state().has_contributed(&member, cycle))
+}
+
+/// View function that returns when each entrypoint that changes the state
+/// was last called successfully, ordered by entrypoint name. Entrypoints
+/// never called are left out.
+#[receive(
+    contract = "dthrift",
+    name = "getActivity",
+    return_value = "Vec<(String, Timestamp)>"
+)]
+fn get_activity<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Vec<(String, Timestamp)>> {
+    Ok(host
+        .state()
+        .activity
+        .iter()
+        .map(|(name, last_called)| (name.clone(), *last_called))
+        .collect())
+}
+
+/// View function that returns the current member who joined most recently
+/// and when, or `None` if no member has joined. Of members who joined at the
+/// same time, the one with the higher user index is returned.
+#[receive(
+    contract = "dthrift",
+    name = "getLastJoined",
+    return_value = "Option<(AccountAddress, Timestamp)>"
+)]
+fn get_last_joined<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Option<(AccountAddress, Timestamp)>> {
+    let state = host.state();
+    let last_joined = state
+        .members
+        .iter()
+        .flatten()
+        .filter_map(|(address, index)| {
+            state.joined_at.get(address).map(|joined_at| (*joined_at, *index, *address))
+        })
+        .max()
+        .map(|(joined_at, _, address)| (address, joined_at));
+    Ok(last_joined)
+}
+
+/// View function that returns whether both accounts are current members
+/// of the club.
+#[receive(
+    contract = "dthrift",
+    name = "areCoMembers",
+    parameter = "(AccountAddress, AccountAddress)",
+    return_value = "bool"
+)]
+fn are_co_members<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<bool> {
+    let (first, second): (AccountAddress, AccountAddress) = ctx.parameter_cursor().get()?;
+    Ok(host.state().is_member(&first) && host.state().is_member(&second))
+}
+
+/// View function that returns, for every contribution cycle up to the
+/// current one, the cycle and how many accounts contributed for it.
+/// Cycles paid for in advance are not counted before they start.
+#[receive(
+    contract = "dthrift",
+    name = "getCycleContributionCounts",
+    return_value = "Vec<(u64, u64)>"
+)]
+fn get_cycle_contribution_counts<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Vec<(u64, u64)>> {
+    let state = host.state();
+    let last_cycle = state
+        .contribution_cycle(ctx.metadata().slot_time())
+        .min(state.payout_cycle.saturating_sub(1));
+    let counts = (0..=last_cycle)
+        .map(|cycle| {
+            let count = state
+                .contributed_cycles
+                .values()
+                .filter(|cycles| cycles.contains(&cycle))
+                .count() as u64;
+            (cycle, count)
+        })
+        .collect();
+    Ok(counts)
+}
+
+/// View function that returns the average share, as a percentage, of the
+/// past contribution cycles the members paid for by their deadline. A
+/// club without past cycles or members is fully compliant.
+#[receive(contract = "dthrift", name = "getClubCompliance", return_value = "u8")]
+fn get_club_compliance<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<u8> {
+    let state = host.state();
+    let now = ctx.metadata().slot_time();
+    let rates: Vec<u64> = state
+        .members
+        .iter()
+        .flatten()
+        .map(|(address, _)| {
+            let past_cycles = state.member_cycle(address, now).min(state.payout_cycle);
+            if past_cycles == 0 {
+                return 100;
+            }
+            let paid = (0..past_cycles)
+                .filter(|cycle| state.has_contributed(address, *cycle))
+                .count() as u64;
+            paid * 100 / past_cycles
+        })
+        .collect();
+    if rates.is_empty() {
+        return Ok(100);
+    }
+    Ok((rates.iter().sum::<u64>() / rates.len() as u64) as u8)
+}
+
+/// View function that returns the most a member can ever pay in: the join
+/// fee, the collateral, the deposit for joining late, every contribution, and the
+/// escalating late penalty for every cycle that can be paid late.
+#[receive(
+    contract = "dthrift",
+    name = "getMaxMemberObligation",
+    return_value = "Amount",
+    error = "Error"
+)]
+fn get_max_member_obligation<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> Result<Amount, Error> {
+    let state = host.state();
+    let mut total = state.join_fee.micro_ccd as u128
+        + state.collateral_amount.micro_ccd as u128
+        + state.penalty_amount.micro_ccd as u128 * state.late_join_multiplier.max(1) as u128;
+    for cycle in 0..state.payout_cycle {
+        total += expected_contribution(state, cycle).micro_ccd as u128;
+    }
+    // Every cycle but the first can be paid late.
+    if !state.auto_penalize {
+        for defaults in 0..state.payout_cycle.saturating_sub(1) {
+            total += state.escalated_penalty(defaults)?.micro_ccd as u128;
+        }
+    }
+    ensure!(total <= u64::MAX as u128, Error::InvalidState);
+    Ok(Amount::from_micro_ccd(total as u64))
+}
+
+/// View function that returns when the next receiver can be paid out: once
+/// the payout phase has opened and the payment interval has passed since
+/// the last payout.
+#[receive(
+    contract = "dthrift",
+    name = "getNextPayoutTime",
+    return_value = "Timestamp",
+    error = "Error"
+)]
+fn get_next_payout_time<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> Result<Timestamp, Error> {
+    host.state().next_payout_time()
+}
+
+/// View function that returns whether the caller is the next receiver and
+/// their payout is due now.
+#[receive(contract = "dthrift", name = "isMyWithdrawalDue", return_value = "bool")]
+fn is_my_withdrawal_due<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<bool> {
+    let state = host.state();
+    let due = state
+        .next_payout_time()
+        .map_or(false, |due_time| ctx.metadata().slot_time() >= due_time);
+    Ok(due && state.next_receiver == Some(ctx.invoker()))
+}
+
+/// View function that returns the total of all contributions if every slot
+/// is filled and every member pays every cycle.
+#[receive(
+    contract = "dthrift",
+    name = "getExpectedTotalPot",
+    return_value = "Amount",
+    error = "Error"
+)]
+fn get_expected_total_pot<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> Result<Amount, Error> {
+    let state = host.state();
+    state
+        .contribution_amount
+        .micro_ccd
+        .checked_mul(state.max_contributors)
+        .and_then(|per_cycle| per_cycle.checked_mul(state.payout_cycle))
+        .map(Amount::from_micro_ccd)
+        .ok_or(Error::InvalidState)
+}
+
+/// View function that returns whether the payout of the given cycle has
+/// been made.
+#[receive(contract = "dthrift", name = "isCyclePaid", parameter = "u64", return_value = "bool")]
+fn is_cycle_paid<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<bool> {
+    let cycle: u64 = ctx.parameter_cursor().get()?;
+    Ok(host.state().is_cycle_paid(cycle))
+}
+
+/// View function that returns the payout cycles the given account received
+/// a payout in, in the order they were paid. With one payout per member
+/// this holds at most one cycle.
+#[receive(
+    contract = "dthrift",
+    name = "getMemberPayoutCycles",
+    parameter = "AccountAddress",
+    return_value = "Vec<u64>"
+)]
+fn get_member_payout_cycles<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Vec<u64>> {
+    let member: AccountAddress = ctx.parameter_cursor().get()?;
+    let cycles = host
+        .state()
+        .completed_cycles
+        .iter()
+        .filter(|(_, receivers)| receivers.contains(&member))
+        .map(|(cycle, _)| *cycle)
+        .collect();
+    Ok(cycles)
+}
+
+/// View function that returns the number of payout cycles left, zero once
+/// the club has completed.
+#[receive(contract = "dthrift", name = "getRemainingCycles", return_value = "u64")]
+fn get_remaining_cycles<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<u64> {
+    let state = host.state();
+    Ok(state.payout_cycle.saturating_sub(state.current_cycle))
+}
+
+/// View function that returns the club's progress through its payout
+/// cycles as a percentage.
+#[receive(contract = "dthrift", name = "getProgress", return_value = "u8")]
+fn get_progress<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<u8> {
+    let state = host.state();
+    if state.payout_cycle == 0 {
+        return Ok(0);
+    }
+    let current_cycle = state.current_cycle.min(state.payout_cycle);
+    let progress = u128::from(current_cycle) * 100 / u128::from(state.payout_cycle);
+    Ok(progress as u8)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use test_infrastructure::*;
+
+    const CREATOR: AccountAddress = AccountAddress([8u8; 32]);
+    const MEMBER_1: AccountAddress = AccountAddress([1u8; 32]);
+    const MEMBER_2: AccountAddress = AccountAddress([2u8; 32]);
+    const BACKUP: AccountAddress = AccountAddress([9u8; 32]);
+
+    const DAY_MILLIS: u64 = 24 * 60 * 60 * 1000;
+
+    /// A club of two members contributing 100 micro CCD every 14 days.
+    fn default_parameter() -> InitParameter {
+        InitParameter {
+            name: "Testing".into(),
+            description: "A very long one".into(),
+            contribution_amount: Amount::from_micro_ccd(100),
+            payout_cycle: 2,
+            start_time: Timestamp::from_timestamp_millis(0),
+            end_time: Timestamp::from_timestamp_millis(100 * DAY_MILLIS),
+            time_interval: Duration::from_days(14),
+            penalty_amount: Amount::from_micro_ccd(10),
+            max_contributors: 2,
+            backup_finalizer_delay: DEFAULT_BACKUP_FINALIZER_DELAY,
+            late_join_multiplier: 3,
+            forfeit_destination: ForfeitDestination::Pot,
+            decimals: None,
+            allow_prepay: false,
+            min_members: DEFAULT_MIN_MEMBERS,
+            end_of_life: EndOfLifePolicy::DistributeEqually,
+            reminder_window: DEFAULT_REMINDER_WINDOW,
+            cosign_threshold: None,
+            join_fee: Amount::zero(),
+            sub_round_size: None,
+            abort_supermajority_bps: DEFAULT_ABORT_SUPERMAJORITY_BPS,
+            max_payout_per_cycle: None,
+            deadline_mode: DeadlineMode::Global,
+            min_streak: 0,
+            payout_quorum_bps: BPS_DENOMINATOR,
+            probation_cycles: 0,
+            escalation_bps: 0,
+            lock_terms: false,
+            final_cycle_tolerance: Amount::zero(),
+            dust_policy: DustPolicy::Members,
+            auto_penalize: false,
+            abort_vote_delay: Duration::from_millis(0),
+            strict_finalize: false,
+            min_join_gap: Duration::from_millis(0),
+            early_bird_count: 0,
+            early_bird_penalty: Amount::zero(),
+            collateral_amount: Amount::zero(),
+            min_pot_for_payout: Amount::zero(),
+            soft_close_bps: None,
+            contribution_cooldown_divisor: DEFAULT_CONTRIBUTION_COOLDOWN_DIVISOR,
+        }
+    }
+
+    /// Initializes the club at time zero with the creator as origin.
+    fn try_init(parameter: &InitParameter) -> InitResult<State> {
+        let parameter_bytes = to_bytes(parameter);
+        let mut ctx = TestInitContext::empty();
+        ctx.set_init_origin(CREATOR);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        tanda_init(&ctx, &mut state_builder)
+    }
+
+    /// Initializes the club and wraps its state in a test host.
+    fn init_host(parameter: &InitParameter) -> TestHost<State> {
+        let state = try_init(parameter).expect_report("Initialization failed");
+        TestHost::new(state, TestStateBuilder::new())
+    }
+
+    impl State {
+        /// The state of a club initialized with the default parameter, with
+        /// the given accounts as members in join order.
+        fn test_default(members: &[AccountAddress]) -> State {
+            let mut state = try_init(&default_parameter()).expect_report("Initialization failed");
+            state.members = Some(members.iter().copied().zip(1..).collect());
+            state.user_index = members.len() as u64;
+            state
+        }
+    }
+
+    /// A receive context for a call by `account` at `now_millis`.
+    fn receive_ctx<'a>(account: AccountAddress, now_millis: u64) -> TestReceiveContext<'a> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(account));
+        ctx.set_invoker(account);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(now_millis));
+        ctx
+    }
+
+    #[concordium_test]
+    fn test_contribute_after_cooldown() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        let mut logger = TestLogger::init();
+        let amount = Amount::from_micro_ccd(100);
+
+        let ctx = receive_ctx(MEMBER_1, 0);
+        let result = contribute(&ctx, &mut host, amount, &mut logger);
+        claim_eq!(result, Ok(()));
+
+        let ctx = receive_ctx(MEMBER_1, 14 * DAY_MILLIS);
+        let result = contribute(&ctx, &mut host, amount, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim_eq!(host.state().total_contributions, Amount::from_micro_ccd(200));
+    }
+
+    #[concordium_test]
+    fn test_contribute_during_cooldown() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        let mut logger = TestLogger::init();
+        let amount = Amount::from_micro_ccd(100);
+
+        let ctx = receive_ctx(MEMBER_1, 0);
+        let result = contribute(&ctx, &mut host, amount, &mut logger);
+        claim_eq!(result, Ok(()));
+
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        let result = contribute(&ctx, &mut host, amount, &mut logger);
+        claim_eq!(result, Err(Error::ContributionCooldown));
+        claim_eq!(host.state().total_contributions, Amount::from_micro_ccd(100));
+    }
+
+    #[concordium_test]
+    fn test_contribution_cooldown_divisor() {
+        // A divisor of one makes the cooldown the whole payment interval.
+        let mut parameter = default_parameter();
+        parameter.contribution_cooldown_divisor = 1;
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        let mut logger = TestLogger::init();
+        let amount = Amount::from_micro_ccd(100);
+
+        let ctx = receive_ctx(MEMBER_1, 7 * DAY_MILLIS);
+        claim_eq!(contribute(&ctx, &mut host, amount, &mut logger), Ok(()));
+
+        // The default cooldown of half an interval would have passed.
+        let ctx = receive_ctx(MEMBER_1, 14 * DAY_MILLIS);
+        claim_eq!(
+            contribute(&ctx, &mut host, amount, &mut logger),
+            Err(Error::ContributionCooldown)
+        );
+
+        let ctx = receive_ctx(MEMBER_1, 21 * DAY_MILLIS);
+        claim_eq!(contribute(&ctx, &mut host, amount, &mut logger), Ok(()));
+        claim_eq!(host.state().total_contributions, Amount::from_micro_ccd(200));
+
+        parameter.contribution_cooldown_divisor = 0;
+        claim_eq!(
+            try_init(&parameter).map(|_| ()),
+            Err(Reject::from(Error::InvalidCooldownDivisor))
+        );
+    }
+
+    #[concordium_test]
+    fn test_get_progress() {
+        let mut host = init_host(&default_parameter());
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(get_progress(&ctx, &host), Ok(0));
+
+        host.state_mut().current_cycle = 1;
+        claim_eq!(get_progress(&ctx, &host), Ok(50));
+
+        host.state_mut().current_cycle = 2;
+        claim_eq!(get_progress(&ctx, &host), Ok(100));
+    }
+
+    #[concordium_test]
+    fn test_get_progress_without_cycles() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().payout_cycle = 0;
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(get_progress(&ctx, &host), Ok(0));
+    }
+
+    /// Sets up a club whose payout cycles are all complete with `BACKUP` as
+    /// backup finalizer.
+    fn completed_host_with_backup() -> TestHost<State> {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        host.state_mut().current_cycle = 2;
+        host.set_self_balance(Amount::from_micro_ccd(400));
+
+        let parameter_bytes = to_bytes(&Some(BACKUP));
+        let mut ctx = receive_ctx(CREATOR, 0);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(set_backup_finalizer(&ctx, &mut host), Ok(()));
+        host
+    }
+
+    #[concordium_test]
+    fn test_backup_finalizer_after_delay() {
+        let mut host = completed_host_with_backup();
+
+        let ctx = receive_ctx(BACKUP, 107 * DAY_MILLIS);
+        claim_eq!(finalize(&ctx, &mut host), Ok(()));
+        claim!(host.state().finalized);
+        claim!(host.transfer_occurred(&MEMBER_1, Amount::from_micro_ccd(400)));
+    }
+
+    #[concordium_test]
+    fn test_backup_finalizer_before_delay() {
+        let mut host = completed_host_with_backup();
+
+        let ctx = receive_ctx(BACKUP, 101 * DAY_MILLIS);
+        claim_eq!(
+            finalize(&ctx, &mut host),
+            Err(Error::FinalizationDelayNotReached)
+        );
+        claim!(!host.state().finalized);
+    }
+
+    /// Sets up a club where `MEMBER_1` is the only member and has contributed.
+    fn contributed_host() -> TestHost<State> {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        host.state_mut().max_contributors = 1;
+        let mut logger = TestLogger::init();
+        let ctx = receive_ctx(MEMBER_1, 0);
+        let result = contribute(&ctx, &mut host, Amount::from_micro_ccd(100), &mut logger);
+        claim_eq!(result, Ok(()));
+        host.set_self_balance(Amount::from_micro_ccd(100));
+        host
+    }
+
+    #[concordium_test]
+    fn test_pending_to_in_progress_on_first_withdraw() {
+        let mut host = contributed_host();
+        let mut logger = TestLogger::init();
+
+        let ctx = receive_ctx(CREATOR, 28 * DAY_MILLIS);
+        claim_eq!(start_withdrawal_phase(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(host.state().tanda_state, TandaState::Pending);
+
+        let ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+        claim_eq!(withdraw(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(host.state().tanda_state, TandaState::InProgress);
+        claim_eq!(
+            host.state().withdrawable_balances.get(&MEMBER_1),
+            Some(&Amount::from_micro_ccd(100))
+        );
+    }
+
+    #[concordium_test]
+    fn test_withdraw_before_withdrawal_phase() {
+        let mut host = contributed_host();
+        let mut logger = TestLogger::init();
+
+        let ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+        claim_eq!(withdraw(&ctx, &mut host, &mut logger), Err(Error::InvalidState));
+        claim_eq!(host.state().tanda_state, TandaState::Open);
+    }
+
+    #[concordium_test]
+    fn test_required_join_amount_before_and_after_late_join() {
+        let host = init_host(&default_parameter());
+
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        claim_eq!(
+            get_required_join_amount(&ctx, &host),
+            Ok(Amount::from_micro_ccd(10))
+        );
+
+        let ctx = receive_ctx(MEMBER_1, 14 * DAY_MILLIS);
+        claim_eq!(
+            get_required_join_amount(&ctx, &host),
+            Ok(Amount::from_micro_ccd(30))
+        );
+    }
+
+    /// Lets `MEMBER_1` forfeit from a club of two members who each paid a
+    /// deposit of 10 micro CCD.
+    fn forfeit_with_destination(destination: ForfeitDestination) -> TestHost<State> {
+        let mut parameter = default_parameter();
+        parameter.forfeit_destination = destination;
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        host.state_mut().deposits.insert(MEMBER_1, Amount::from_micro_ccd(10));
+        host.state_mut().deposits.insert(MEMBER_2, Amount::from_micro_ccd(10));
+        host.set_self_balance(Amount::from_micro_ccd(20));
+
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        claim_eq!(forfeit(&ctx, &mut host), Ok(()));
+        claim!(!host.state().is_member(&MEMBER_1));
+        claim_eq!(host.state().deposits.get(&MEMBER_1), None);
+        host
+    }
+
+    #[concordium_test]
+    fn test_forfeit_to_pot() {
+        let host = forfeit_with_destination(ForfeitDestination::Pot);
+        claim_eq!(host.state().total_contributions, Amount::from_micro_ccd(10));
+        claim_eq!(host.state().deposits.get(&MEMBER_2), Some(&Amount::from_micro_ccd(10)));
+        claim!(host.get_transfers().is_empty());
+    }
+
+    #[concordium_test]
+    fn test_forfeit_to_members() {
+        let host = forfeit_with_destination(ForfeitDestination::Members);
+        claim_eq!(host.state().total_contributions, Amount::zero());
+        claim_eq!(host.state().deposits.get(&MEMBER_2), Some(&Amount::from_micro_ccd(20)));
+        claim!(host.get_transfers().is_empty());
+    }
+
+    #[concordium_test]
+    fn test_forfeit_to_creator() {
+        let host = forfeit_with_destination(ForfeitDestination::Creator);
+        claim_eq!(host.state().total_contributions, Amount::zero());
+        claim_eq!(host.state().deposits.get(&MEMBER_2), Some(&Amount::from_micro_ccd(10)));
+        claim!(host.transfer_occurred(&CREATOR, Amount::from_micro_ccd(10)));
+    }
+
+    #[concordium_test]
+    fn test_get_last_payout() {
+        let mut host = contributed_host();
+        let mut logger = TestLogger::init();
+
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(get_last_payout(&ctx, &host), Ok(Amount::zero()));
+
+        let ctx = receive_ctx(CREATOR, 28 * DAY_MILLIS);
+        claim_eq!(start_withdrawal_phase(&ctx, &mut host, &mut logger), Ok(()));
+        let ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+        claim_eq!(withdraw(&ctx, &mut host, &mut logger), Ok(()));
+
+        claim_eq!(get_last_payout(&ctx, &host), Ok(Amount::from_micro_ccd(100)));
+    }
+
+    #[concordium_test]
+    fn test_member_finalizes() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        host.state_mut().current_cycle = 2;
+        host.set_self_balance(Amount::from_micro_ccd(50));
+
+        let ctx = receive_ctx(MEMBER_1, 100 * DAY_MILLIS);
+        claim_eq!(finalize(&ctx, &mut host), Ok(()));
+        claim!(host.transfer_occurred(&MEMBER_1, Amount::from_micro_ccd(50)));
+    }
+
+    #[concordium_test]
+    fn test_non_member_cannot_finalize() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        host.state_mut().current_cycle = 2;
+        host.set_self_balance(Amount::from_micro_ccd(50));
+
+        let ctx = receive_ctx(MEMBER_2, 100 * DAY_MILLIS);
+        claim_eq!(finalize(&ctx, &mut host), Err(Error::NotJoined));
+        claim!(!host.state().finalized);
+        claim!(host.get_transfers().is_empty());
+    }
+
+    #[concordium_test]
+    fn test_decimals() {
+        let host = init_host(&default_parameter());
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(view(&ctx, &host).map(|state| state.decimals), Ok(6));
+
+        let mut parameter = default_parameter();
+        parameter.decimals = Some(8);
+        let host = init_host(&parameter);
+        claim_eq!(view(&ctx, &host).map(|state| state.decimals), Ok(8));
+        claim_eq!(host.state().contribution_amount, Amount::from_micro_ccd(100));
+    }
+
+    #[concordium_test]
+    fn test_withdraw_without_next_receiver() {
+        let mut host = contributed_host();
+        host.state_mut().tanda_state = TandaState::Pending;
+        host.state_mut().withdrawal_phase_started = true;
+        claim_eq!(host.state().next_receiver, None);
+        let mut logger = TestLogger::init();
+
+        let ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+        claim_eq!(withdraw(&ctx, &mut host, &mut logger), Err(Error::InvalidState));
+        claim!(host.get_transfers().is_empty());
+        claim_eq!(host.state().tanda_state, TandaState::Pending);
+    }
+
+    /// Sets up a club of one member whose first cycle was paid out at day 28.
+    fn paid_out_host() -> TestHost<State> {
+        let mut host = contributed_host();
+        let mut logger = TestLogger::init();
+        let ctx = receive_ctx(CREATOR, 28 * DAY_MILLIS);
+        claim_eq!(start_withdrawal_phase(&ctx, &mut host, &mut logger), Ok(()));
+        let ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+        claim_eq!(withdraw(&ctx, &mut host, &mut logger), Ok(()));
+        host
+    }
+
+    #[concordium_test]
+    fn test_advance_cycle() {
+        let mut host = paid_out_host();
+        // Another member is still waiting for the next cycle's payout.
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+
+        let ctx = receive_ctx(CREATOR, 42 * DAY_MILLIS);
+        claim_eq!(advance_cycle_manually(&ctx, &mut host), Ok(()));
+        claim_eq!(host.state().current_cycle, 1);
+        claim_eq!(host.state().tanda_state, TandaState::InProgress);
+    }
+
+    #[concordium_test]
+    fn test_advance_past_last_cycle_completes() {
+        let mut host = paid_out_host();
+        host.state_mut().payout_cycle = 1;
+
+        let ctx = receive_ctx(CREATOR, 42 * DAY_MILLIS);
+        claim_eq!(advance_cycle_manually(&ctx, &mut host), Ok(()));
+        claim_eq!(host.state().tanda_state, TandaState::Completed);
+    }
+
+    #[concordium_test]
+    fn test_advance_cycle_prematurely() {
+        let mut host = paid_out_host();
+
+        let ctx = receive_ctx(CREATOR, 30 * DAY_MILLIS);
+        claim_eq!(
+            advance_cycle_manually(&ctx, &mut host),
+            Err(Error::WithdrawalIntervalNotReached)
+        );
+        claim_eq!(host.state().current_cycle, 0);
+    }
+
+    #[concordium_test]
+    fn test_export_snapshot_is_deterministic() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_2, 1), (MEMBER_1, 2)]);
+        host.state_mut().contributors.insert(MEMBER_2);
+        host.state_mut().contributors.insert(MEMBER_1);
+        let ctx = receive_ctx(MEMBER_1, 0);
+
+        let first = export_snapshot(&ctx, &host).expect_report("Export failed");
+        let second = export_snapshot(&ctx, &host).expect_report("Export failed");
+        claim_eq!(first.version, SNAPSHOT_VERSION);
+        claim_eq!(to_bytes(&first), to_bytes(&second));
+    }
+
+    #[concordium_test]
+    fn test_get_state_hash() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        let ctx = receive_ctx(MEMBER_1, 0);
+        let crypto_primitives = TestCryptoPrimitives::new();
+        // Stands in for SHA-256: any change of the input changes the output.
+        crypto_primitives.setup_hash_sha2_256_mock(|data| {
+            let mut hash = [0u8; 32];
+            for (i, byte) in data.iter().enumerate() {
+                hash[i % 32] = hash[i % 32].rotate_left(3) ^ byte;
+            }
+            hash[31] ^= data.len() as u8;
+            HashSha2256(hash)
+        });
+
+        let first = get_state_hash(&ctx, &host, &crypto_primitives);
+        claim_eq!(get_state_hash(&ctx, &host, &crypto_primitives), first);
+
+        host.state_mut().contributors.insert(MEMBER_1);
+        let changed = get_state_hash(&ctx, &host, &crypto_primitives);
+        claim!(changed != first);
+        claim_eq!(get_state_hash(&ctx, &host, &crypto_primitives), changed);
+    }
+
+    #[concordium_test]
+    fn test_init_rejects_zero_time_interval() {
+        let mut parameter = default_parameter();
+        parameter.time_interval = Duration::from_millis(0);
+        claim_eq!(
+            try_init(&parameter).map(|_| ()),
+            Err(Reject::from(Error::InvalidTimeInterval))
+        );
+    }
+
+    #[concordium_test]
+    fn test_refundable_deposit_after_partial_slash() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        host.state_mut().deposits.insert(MEMBER_1, Amount::from_micro_ccd(10));
+
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(
+            get_my_refundable_deposit(&ctx, &host),
+            Ok(Amount::from_micro_ccd(10))
+        );
+
+        let parameter_bytes = to_bytes(&SlashParameter {
+            member: MEMBER_1,
+            amount: Amount::from_micro_ccd(4),
+        });
+        let mut ctx = receive_ctx(CREATOR, DAY_MILLIS);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(slash(&ctx, &mut host), Ok(()));
+
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        claim_eq!(
+            get_my_refundable_deposit(&ctx, &host),
+            Ok(Amount::from_micro_ccd(6))
+        );
+        claim_eq!(host.state().total_contributions, Amount::from_micro_ccd(4));
+    }
+
+    #[concordium_test]
+    fn test_prepay_covers_several_cycles() {
+        let mut parameter = default_parameter();
+        parameter.payout_cycle = 3;
+        parameter.max_contributors = 3;
+        parameter.allow_prepay = true;
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        let mut logger = TestLogger::init();
+
+        let ctx = receive_ctx(MEMBER_1, 0);
+        let result = contribute(&ctx, &mut host, Amount::from_micro_ccd(300), &mut logger);
+        claim_eq!(result, Ok(()));
+        let expected_cycles: BTreeSet<u64> = (0..3).collect();
+        claim_eq!(host.state().contributed_cycles.get(&MEMBER_1), Some(&expected_cycles));
+
+        // All three cycles are covered, so no further payment is needed or accepted.
+        for cycle in 1..3 {
+            let ctx = receive_ctx(MEMBER_1, cycle * 14 * DAY_MILLIS);
+            let result = contribute(&ctx, &mut host, Amount::from_micro_ccd(100), &mut logger);
+            claim_eq!(result, Err(Error::InvalidContributionAmount));
+        }
+        claim_eq!(host.state().total_contributions, Amount::from_micro_ccd(300));
+    }
+
+    #[concordium_test]
+    fn test_prepay_not_allowed() {
+        let mut parameter = default_parameter();
+        parameter.payout_cycle = 3;
+        parameter.max_contributors = 3;
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        let mut logger = TestLogger::init();
+
+        let ctx = receive_ctx(MEMBER_1, 0);
+        let result = contribute(&ctx, &mut host, Amount::from_micro_ccd(300), &mut logger);
+        claim_eq!(result, Err(Error::InvalidContributionAmount));
+    }
+
+    #[concordium_test]
+    fn test_contribute_twice_in_one_cycle() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        let mut logger = TestLogger::init();
+        let amount = Amount::from_micro_ccd(100);
+
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(contribute(&ctx, &mut host, amount, &mut logger), Ok(()));
+        let ctx = receive_ctx(MEMBER_1, 8 * DAY_MILLIS);
+        claim_eq!(
+            contribute(&ctx, &mut host, amount, &mut logger),
+            Err(Error::AlreadyContributed)
+        );
+    }
+
+    #[concordium_test]
+    fn test_get_prepaid_members() {
+        let mut parameter = default_parameter();
+        parameter.payout_cycle = 4;
+        parameter.max_contributors = 4;
+        parameter.allow_prepay = true;
+        parameter.end_time = Timestamp::from_timestamp_millis(112 * DAY_MILLIS);
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        let mut logger = TestLogger::init();
+
+        let ctx = receive_ctx(MEMBER_1, 0);
+        let result = contribute(&ctx, &mut host, Amount::from_micro_ccd(300), &mut logger);
+        claim_eq!(result, Ok(()));
+        let ctx = receive_ctx(MEMBER_2, 0);
+        let result = contribute(&ctx, &mut host, Amount::from_micro_ccd(100), &mut logger);
+        claim_eq!(result, Ok(()));
+
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(get_prepaid_members(&ctx, &host), Ok(vec![(MEMBER_1, 2)]));
+
+        let ctx = receive_ctx(MEMBER_1, 14 * DAY_MILLIS);
+        claim_eq!(get_prepaid_members(&ctx, &host), Ok(vec![(MEMBER_1, 1)]));
+    }
+
+    #[concordium_test]
+    fn test_refund_large_club_in_batches() {
+        let mut host = init_host(&default_parameter());
+        let members: Vec<(AccountAddress, u64)> =
+            (1..=25u8).map(|i| (AccountAddress([i; 32]), u64::from(i))).collect();
+        for (member, _) in members.iter() {
+            host.state_mut().deposits.insert(*member, Amount::from_micro_ccd(10));
+        }
+        host.state_mut().members = Some(members.clone());
+        host.state_mut().tanda_state = TandaState::Completed;
+        host.set_self_balance(Amount::from_micro_ccd(250));
+
+        let mut cursor = 0u64;
+        let mut calls = 0;
+        loop {
+            let parameter_bytes = to_bytes(&cursor);
+            let mut ctx = receive_ctx(MEMBER_1, 100 * DAY_MILLIS);
+            ctx.set_parameter(&parameter_bytes);
+            let result = refund_batch(&ctx, &mut host).expect_report("Refund failed");
+            calls += 1;
+            match result {
+                Some(next_cursor) => cursor = next_cursor,
+                None => break,
+            }
+        }
+
+        claim_eq!(calls, 3);
+        claim!(host.state().deposits.is_empty());
+        claim_eq!(host.get_transfers().len(), 25);
+        for (member, _) in members.iter() {
+            claim!(host.transfer_occurred(member, Amount::from_micro_ccd(10)));
+        }
+    }
+
+    #[concordium_test]
+    fn test_expected_contribution_is_fixed() {
+        let host = init_host(&default_parameter());
+        for cycle in 0..2 {
+            claim_eq!(
+                expected_contribution(host.state(), cycle),
+                Amount::from_micro_ccd(100)
+            );
+        }
+    }
+
+    #[concordium_test]
+    fn test_close_joining_early() {
+        let mut parameter = default_parameter();
+        parameter.max_contributors = 3;
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+
+        let ctx = receive_ctx(CREATOR, DAY_MILLIS);
+        claim_eq!(close_joining(&ctx, &mut host), Ok(()));
+        claim_eq!(host.state().tanda_state, TandaState::Closed);
+
+        // Members keep contributing after joining is closed.
+        let mut logger = TestLogger::init();
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        let result = contribute(&ctx, &mut host, Amount::from_micro_ccd(100), &mut logger);
+        claim_eq!(result, Ok(()));
+    }
+
+    #[concordium_test]
+    fn test_close_joining_below_minimum() {
+        let mut parameter = default_parameter();
+        parameter.max_contributors = 3;
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+
+        let ctx = receive_ctx(CREATOR, DAY_MILLIS);
+        claim_eq!(close_joining(&ctx, &mut host), Err(Error::MinimumNotReached));
+        claim_eq!(host.state().tanda_state, TandaState::Open);
+    }
+
+    #[concordium_test]
+    fn test_rotation_order_matches_selection() {
+        let mut host = init_host(&default_parameter());
+        let member_3 = AccountAddress([3u8; 32]);
+        host.state_mut().members = Some(vec![(MEMBER_2, 2), (member_3, 3), (MEMBER_1, 1)]);
+        let ctx = receive_ctx(MEMBER_1, 0);
+        let order = get_rotation_order(&ctx, &host).expect_report("Query failed");
+
+        let mut state = host.state().clone();
+        let mut selected = Vec::new();
+        while let Some(receiver) = select_next_receiver(&state) {
+            selected.push(receiver);
+            state.withdrawn_addresses.insert(receiver);
+        }
+        claim_eq!(order, selected);
+        claim_eq!(order, vec![MEMBER_1, MEMBER_2, member_3]);
+    }
+
+    /// Finalizes a completed club of two members holding a leftover of 101
+    /// micro CCD under the given policy.
+    fn finalize_with_policy(end_of_life: EndOfLifePolicy) -> TestHost<State> {
+        let mut parameter = default_parameter();
+        parameter.end_of_life = end_of_life;
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        host.state_mut().current_cycle = 2;
+        host.set_self_balance(Amount::from_micro_ccd(101));
+
+        let ctx = receive_ctx(MEMBER_1, 100 * DAY_MILLIS);
+        claim_eq!(finalize(&ctx, &mut host), Ok(()));
+        host
+    }
+
+    #[concordium_test]
+    fn test_end_of_life_distribute_equally() {
+        let host = finalize_with_policy(EndOfLifePolicy::DistributeEqually);
+        claim!(host.transfer_occurred(&MEMBER_1, Amount::from_micro_ccd(51)));
+        claim!(host.transfer_occurred(&MEMBER_2, Amount::from_micro_ccd(50)));
+        claim_eq!(host.get_transfers().len(), 2);
+    }
+
+    #[concordium_test]
+    fn test_dust_policy() {
+        let transferred = |host: &TestHost<State>| {
+            host.get_transfers()
+                .iter()
+                .fold(Amount::zero(), |total, (_, amount)| total + *amount)
+        };
+
+        // The member who joined first gets the remainder.
+        let host = finalize_with_policy(EndOfLifePolicy::DistributeEqually);
+        claim!(host.transfer_occurred(&MEMBER_1, Amount::from_micro_ccd(51)));
+        claim_eq!(transferred(&host), Amount::from_micro_ccd(101));
+        claim_eq!(host.self_balance(), Amount::zero());
+
+        let mut parameter = default_parameter();
+        parameter.dust_policy = DustPolicy::Finalizer;
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        host.state_mut().current_cycle = 2;
+        host.set_self_balance(Amount::from_micro_ccd(101));
+
+        let ctx = receive_ctx(MEMBER_2, 100 * DAY_MILLIS);
+        claim_eq!(preview_finalize_remainder(&ctx, &host), Ok(Amount::from_micro_ccd(101)));
+        claim_eq!(finalize(&ctx, &mut host), Ok(()));
+        claim!(host.transfer_occurred(&MEMBER_1, Amount::from_micro_ccd(50)));
+        claim!(host.transfer_occurred(&MEMBER_2, Amount::from_micro_ccd(50)));
+        claim!(host.transfer_occurred(&MEMBER_2, Amount::from_micro_ccd(1)));
+        claim_eq!(transferred(&host), Amount::from_micro_ccd(101));
+        claim_eq!(host.self_balance(), Amount::zero());
+    }
+
+    #[concordium_test]
+    fn test_end_of_life_to_creator() {
+        let host = finalize_with_policy(EndOfLifePolicy::ToCreator);
+        claim!(host.transfer_occurred(&CREATOR, Amount::from_micro_ccd(101)));
+        claim_eq!(host.get_transfers().len(), 1);
+    }
+
+    #[concordium_test]
+    fn test_end_of_life_burn() {
+        let host = finalize_with_policy(EndOfLifePolicy::Burn);
+        claim!(host.get_transfers().is_empty());
+        claim_eq!(host.self_balance(), Amount::from_micro_ccd(101));
+    }
+
+    #[concordium_test]
+    fn test_strict_finalize() {
+        let mut parameter = default_parameter();
+        parameter.strict_finalize = true;
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        host.state_mut().current_cycle = 2;
+
+        // All cycles are complete, but the end time is not reached yet.
+        let ctx = receive_ctx(MEMBER_1, 60 * DAY_MILLIS);
+        claim_eq!(can_finalize(&ctx, &host), Ok(FinalizeEligibility::NotStarted));
+        claim_eq!(finalize(&ctx, &mut host), Err(Error::NotStarted));
+        claim!(!host.state().finalized);
+
+        let ctx = receive_ctx(MEMBER_1, 100 * DAY_MILLIS);
+        claim_eq!(can_finalize(&ctx, &host), Ok(FinalizeEligibility::Ok));
+        claim_eq!(finalize(&ctx, &mut host), Ok(()));
+        claim!(host.state().finalized);
+    }
+
+    #[concordium_test]
+    fn test_claim_credited_payout() {
+        let mut host = paid_out_host();
+        claim!(host.get_transfers().is_empty());
+        claim_eq!(
+            host.state().withdrawable_balances.get(&MEMBER_1),
+            Some(&Amount::from_micro_ccd(100))
+        );
+
+        let ctx = receive_ctx(MEMBER_1, 29 * DAY_MILLIS);
+        claim_eq!(claim_payout(&ctx, &mut host), Ok(()));
+        claim!(host.transfer_occurred(&MEMBER_1, Amount::from_micro_ccd(100)));
+        claim!(host.state().withdrawable_balances.is_empty());
+
+        claim_eq!(claim_payout(&ctx, &mut host), Err(Error::NothingToClaim));
+        claim_eq!(host.get_transfers().len(), 1);
+    }
+
+    #[concordium_test]
+    fn test_all_contributed_this_cycle() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        let mut logger = TestLogger::init();
+        let amount = Amount::from_micro_ccd(100);
+
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        claim_eq!(contribute(&ctx, &mut host, amount, &mut logger), Ok(()));
+        claim_eq!(all_contributed_this_cycle(&ctx, &host), Ok(false));
+
+        let ctx = receive_ctx(MEMBER_2, DAY_MILLIS);
+        claim_eq!(contribute(&ctx, &mut host, amount, &mut logger), Ok(()));
+        claim_eq!(all_contributed_this_cycle(&ctx, &host), Ok(true));
+    }
+
+    #[concordium_test]
+    fn test_missing_account_is_skipped() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        host.state_mut().deposits.insert(MEMBER_1, Amount::from_micro_ccd(10));
+        host.state_mut().deposits.insert(MEMBER_2, Amount::from_micro_ccd(10));
+        host.state_mut().tanda_state = TandaState::Completed;
+        host.set_self_balance(Amount::from_micro_ccd(20));
+        host.make_account_missing(MEMBER_1);
+
+        let parameter_bytes = to_bytes(&0u64);
+        let mut ctx = receive_ctx(MEMBER_2, 100 * DAY_MILLIS);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(refund_batch(&ctx, &mut host), Ok(None));
+
+        claim!(host.transfer_occurred(&MEMBER_2, Amount::from_micro_ccd(10)));
+        claim_eq!(host.get_transfers().len(), 1);
+        claim_eq!(host.state().skipped_receivers, vec![MEMBER_1]);
+        claim_eq!(host.state().deposits.get(&MEMBER_1), Some(&Amount::from_micro_ccd(10)));
+        claim_eq!(select_next_receiver(host.state()), Some(MEMBER_2));
+    }
+
+    #[concordium_test]
+    fn test_emit_reminder_once_per_cycle() {
+        let mut host = init_host(&default_parameter());
+        let mut logger = TestLogger::init();
+
+        let ctx = receive_ctx(MEMBER_1, 13 * DAY_MILLIS);
+        claim_eq!(emit_reminder(&ctx, &mut host, &mut logger), Ok(()));
+        let expected_event = Event::Reminder(ReminderEvent {
+            cycle: 0,
+            deadline: Timestamp::from_timestamp_millis(14 * DAY_MILLIS),
+        });
+        claim_eq!(logger.logs, vec![to_bytes(&expected_event)]);
+
+        // A second reminder in the same cycle is a no-op.
+        let state = host.state().clone();
+        let ctx = receive_ctx(MEMBER_1, 13 * DAY_MILLIS + 1);
+        claim_eq!(emit_reminder(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(logger.logs.len(), 1);
+        claim_eq!(*host.state(), state);
+
+        // There are no deadlines left once the contribution cycles are over.
+        let ctx = receive_ctx(MEMBER_1, 41 * DAY_MILLIS);
+        claim_eq!(
+            emit_reminder(&ctx, &mut host, &mut logger),
+            Err(Error::InvalidPayoutCycle)
+        );
+    }
+
+    #[concordium_test]
+    fn test_emit_reminder_outside_window() {
+        let mut host = init_host(&default_parameter());
+        let mut logger = TestLogger::init();
+
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        claim_eq!(
+            emit_reminder(&ctx, &mut host, &mut logger),
+            Err(Error::OutsideReminderWindow)
+        );
+        claim!(logger.logs.is_empty());
+    }
+
+    /// Sets up a club of one member ready for its first payout of 100
+    /// micro CCD, with the given co-sign threshold.
+    fn cosign_host(threshold: Amount) -> TestHost<State> {
+        let mut host = contributed_host();
+        host.state_mut().cosign_threshold = Some(threshold);
+        let mut logger = TestLogger::init();
+        let ctx = receive_ctx(CREATOR, 28 * DAY_MILLIS);
+        claim_eq!(start_withdrawal_phase(&ctx, &mut host, &mut logger), Ok(()));
+        host
+    }
+
+    #[concordium_test]
+    fn test_withdraw_below_cosign_threshold() {
+        let mut host = cosign_host(Amount::from_micro_ccd(100));
+        let mut logger = TestLogger::init();
+
+        let ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+        claim_eq!(withdraw(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(
+            host.state().withdrawable_balances.get(&MEMBER_1),
+            Some(&Amount::from_micro_ccd(100))
+        );
+    }
+
+    #[concordium_test]
+    fn test_withdraw_above_cosign_threshold() {
+        let mut host = cosign_host(Amount::from_micro_ccd(50));
+        let mut logger = TestLogger::init();
+
+        let ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+        claim_eq!(withdraw(&ctx, &mut host, &mut logger), Err(Error::CosignRequired));
+        claim_eq!(request_withdraw(&ctx, &mut host), Ok(()));
+        claim_eq!(host.state().pending_withdrawal, Some(MEMBER_1));
+        claim!(host.state().withdrawable_balances.is_empty());
+
+        let ctx = receive_ctx(CREATOR, 29 * DAY_MILLIS);
+        claim_eq!(approve_withdraw(&ctx, &mut host), Ok(()));
+        claim_eq!(host.state().pending_withdrawal, None);
+        claim_eq!(
+            host.state().withdrawable_balances.get(&MEMBER_1),
+            Some(&Amount::from_micro_ccd(100))
+        );
+        claim_eq!(approve_withdraw(&ctx, &mut host), Err(Error::NoPendingWithdrawal));
+    }
+
+    #[concordium_test]
+    fn test_approve_withdraw_corrupted_receiver() {
+        let mut host = cosign_host(Amount::from_micro_ccd(50));
+        let ctx = receive_ctx(CREATOR, 29 * DAY_MILLIS);
+
+        for receiver in [NULL_ACCOUNT, BACKUP] {
+            host.state_mut().pending_withdrawal = Some(receiver);
+            claim_eq!(approve_withdraw(&ctx, &mut host), Err(Error::InvalidAddress));
+            claim!(host.state().withdrawable_balances.is_empty());
+            claim!(host.get_transfers().is_empty());
+        }
+    }
+
+    #[concordium_test]
+    fn test_did_contribute() {
+        let host = contributed_host();
+
+        let paid = to_bytes(&(MEMBER_1, 0u64));
+        let mut ctx = receive_ctx(MEMBER_2, 30 * DAY_MILLIS);
+        ctx.set_parameter(&paid);
+        claim_eq!(did_contribute(&ctx, &host), Ok(true));
+
+        let missed = to_bytes(&(MEMBER_1, 1u64));
+        ctx.set_parameter(&missed);
+        claim_eq!(did_contribute(&ctx, &host), Ok(false));
+
+        let future = to_bytes(&(MEMBER_1, 5u64));
+        ctx.set_parameter(&future);
+        claim_eq!(did_contribute(&ctx, &host), Ok(false));
+    }
+
+    #[concordium_test]
+    fn test_contribute_on_time_and_late_amounts() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        let mut logger = TestLogger::init();
+
+        // On time, the contribution amount alone is due.
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(
+            contribute(&ctx, &mut host, Amount::from_micro_ccd(110), &mut logger),
+            Err(Error::InvalidContributionAmount)
+        );
+        claim_eq!(
+            contribute(&ctx, &mut host, Amount::from_micro_ccd(100), &mut logger),
+            Ok(())
+        );
+        let ctx = receive_ctx(MEMBER_1, 14 * DAY_MILLIS);
+        claim_eq!(
+            contribute(&ctx, &mut host, Amount::from_micro_ccd(100), &mut logger),
+            Ok(())
+        );
+
+        // MEMBER_2 missed the first cycle and owes the penalty on top.
+        let ctx = receive_ctx(MEMBER_2, 14 * DAY_MILLIS);
+        claim_eq!(
+            contribute(&ctx, &mut host, Amount::from_micro_ccd(100), &mut logger),
+            Err(Error::Penalized)
+        );
+        claim_eq!(
+            contribute(&ctx, &mut host, Amount::from_micro_ccd(110), &mut logger),
+            Ok(())
+        );
+        claim_eq!(host.state().total_contributions, Amount::from_micro_ccd(310));
+        claim!(host.state().has_contributed(&MEMBER_2, 1));
+    }
+
+    #[concordium_test]
+    fn test_rename() {
+        let mut host = init_host(&default_parameter());
+        let mut logger = TestLogger::init();
+
+        let parameter_bytes = to_bytes(&String::from("Savings"));
+        let mut ctx = receive_ctx(CREATOR, 0);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(rename(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(host.state().name, String::from("Savings"));
+        let expected_event = Event::Renamed(RenamedEvent {
+            name: "Savings".into(),
+        });
+        claim_eq!(logger.logs, vec![to_bytes(&expected_event)]);
+    }
+
+    #[concordium_test]
+    fn test_rename_empty_name() {
+        let mut host = init_host(&default_parameter());
+        let mut logger = TestLogger::init();
+
+        let parameter_bytes = to_bytes(&String::new());
+        let mut ctx = receive_ctx(CREATOR, 0);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(rename(&ctx, &mut host, &mut logger), Err(Error::InvalidName));
+        claim_eq!(host.state().name, String::from("Testing"));
+    }
+
+    #[concordium_test]
+    fn test_rename_after_start() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().tanda_state = TandaState::Pending;
+        let mut logger = TestLogger::init();
+
+        let parameter_bytes = to_bytes(&String::from("Savings"));
+        let mut ctx = receive_ctx(CREATOR, 14 * DAY_MILLIS);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(rename(&ctx, &mut host, &mut logger), Err(Error::TandaClosed));
+        claim!(logger.logs.is_empty());
+    }
+
+    #[concordium_test]
+    fn test_contribute_after_completion() {
+        let mut host = contributed_host();
+        host.state_mut().tanda_state = TandaState::Completed;
+        let mut logger = TestLogger::init();
+
+        let ctx = receive_ctx(MEMBER_1, 14 * DAY_MILLIS);
+        claim_eq!(
+            contribute(&ctx, &mut host, Amount::from_micro_ccd(100), &mut logger),
+            Err(Error::AlreadyFinished)
+        );
+        claim_eq!(host.state().total_contributions, Amount::from_micro_ccd(100));
+    }
+
+    #[concordium_test]
+    fn test_member_metadata() {
+        let mut host = contributed_host();
+
+        let metadata = vec![7u8; 64];
+        let parameter_bytes = to_bytes(&metadata);
+        let mut ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(set_member_metadata(&ctx, &mut host), Ok(()));
+
+        let parameter_bytes = to_bytes(&MEMBER_1);
+        ctx.set_parameter(&parameter_bytes);
+        let status = get_member_status(&ctx, &host);
+        claim_eq!(
+            status,
+            Ok(MemberStatus {
+                index: 1,
+                deposit: Amount::zero(),
+                contributed_this_cycle: true,
+                withdrawn: false,
+                metadata,
+            })
+        );
+    }
+
+    #[concordium_test]
+    fn test_member_metadata_too_long() {
+        let mut host = contributed_host();
+
+        let parameter_bytes = to_bytes(&vec![7u8; 65]);
+        let mut ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            set_member_metadata(&ctx, &mut host),
+            Err(Error::MetadataTooLong)
+        );
+        claim!(host.state().member_metadata.is_empty());
+    }
+
+    #[concordium_test]
+    fn test_is_accepting_contributions() {
+        let mut parameter = default_parameter();
+        parameter.start_time = Timestamp::from_timestamp_millis(DAY_MILLIS);
+        let mut host = init_host(&parameter);
+
+        let ctx = receive_ctx(MEMBER_1, 2 * DAY_MILLIS);
+        claim_eq!(is_accepting_contributions(&ctx, &host), Ok(true));
+
+        host.state_mut().tanda_state = TandaState::Closed;
+        claim_eq!(is_accepting_contributions(&ctx, &host), Ok(true));
+
+        // Before the start time, and after the last cycle.
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(is_accepting_contributions(&ctx, &host), Ok(false));
+        let ctx = receive_ctx(MEMBER_1, 30 * DAY_MILLIS);
+        claim_eq!(is_accepting_contributions(&ctx, &host), Ok(false));
+
+        let ctx = receive_ctx(MEMBER_1, 2 * DAY_MILLIS);
+        host.state_mut().tanda_state = TandaState::Pending;
+        claim_eq!(is_accepting_contributions(&ctx, &host), Ok(false));
+        host.state_mut().tanda_state = TandaState::Completed;
+        claim_eq!(is_accepting_contributions(&ctx, &host), Ok(false));
+    }
+
+    #[concordium_test]
+    fn test_join_fee_collected_and_not_refunded() {
+        let mut parameter = default_parameter();
+        parameter.join_fee = Amount::from_micro_ccd(5);
+        let mut host = init_host(&parameter);
+        let mut logger = TestLogger::init();
+
+        let parameter_bytes = to_bytes(&JoinTandaParameter { penalty_amount: 10 });
+        let mut ctx = receive_ctx(MEMBER_1, 0);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            join_tanda(&ctx, &mut host, Amount::from_micro_ccd(10), &mut logger),
+            Err(Error::InvalidPenaltyAmount)
+        );
+        claim_eq!(
+            join_tanda(&ctx, &mut host, Amount::from_micro_ccd(15), &mut logger),
+            Ok(())
+        );
+        claim_eq!(host.state().deposits.get(&MEMBER_1), Some(&Amount::from_micro_ccd(10)));
+        claim_eq!(host.state().total_contributions, Amount::from_micro_ccd(5));
+
+        // Leaving forfeits the deposit to the pot; the fee is never paid back.
+        host.set_self_balance(Amount::from_micro_ccd(15));
+        claim_eq!(forfeit(&ctx, &mut host), Ok(()));
+        claim!(host.get_transfers().is_empty());
+        claim_eq!(host.state().total_contributions, Amount::from_micro_ccd(15));
+    }
+
+    #[concordium_test]
+    fn test_join_fee_above_contribution() {
+        let mut parameter = default_parameter();
+        parameter.join_fee = Amount::from_micro_ccd(101);
+        claim_eq!(
+            try_init(&parameter).map(|_| ()),
+            Err(Reject::from(Error::InvalidJoinFee))
+        );
+    }
+
+    #[concordium_test]
+    fn test_sub_rounds() {
+        let mut parameter = default_parameter();
+        parameter.sub_round_size = Some(3);
+        parameter.payout_cycle = 6;
+        parameter.max_contributors = 6;
+        parameter.end_time = Timestamp::from_timestamp_millis(168 * DAY_MILLIS);
+        let mut host = init_host(&parameter);
+        let members: Vec<(AccountAddress, u64)> =
+            (1..=6).map(|i| (AccountAddress([i as u8; 32]), i)).collect();
+        host.state_mut().members = Some(members.clone());
+        host.state_mut().next_receiver = select_next_receiver(host.state());
+
+        for (cycle, (address, _)) in members.iter().enumerate() {
+            let ctx = receive_ctx(MEMBER_1, 0);
+            let expected_sub_round = cycle as u64 / 3;
+            claim_eq!(get_sub_round(&ctx, &host), Ok(expected_sub_round));
+            claim_eq!(host.state().next_receiver, Some(*address));
+            let now = Timestamp::from_timestamp_millis(cycle as u64 * 14 * DAY_MILLIS);
+            claim_eq!(pay_out(host.state_mut(), *address, cycle as u64, now), Ok(()));
+        }
+        claim_eq!(host.state().sub_round, 1);
+        claim_eq!(host.state().next_receiver, None);
+    }
+
+    #[concordium_test]
+    fn test_get_age() {
+        let host = init_host(&default_parameter());
+
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(get_age(&ctx, &host), Ok(Duration::from_millis(0)));
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        claim_eq!(get_age(&ctx, &host), Ok(Duration::from_days(1)));
+    }
+
+    #[concordium_test]
+    fn test_get_join_deadline_eta() {
+        let mut host = init_host(&default_parameter());
+
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(get_join_deadline_eta(&ctx, &host), Ok(Some(Duration::from_days(100))));
+        let ctx = receive_ctx(MEMBER_1, 99 * DAY_MILLIS);
+        claim_eq!(get_join_deadline_eta(&ctx, &host), Ok(Some(Duration::from_days(1))));
+        let ctx = receive_ctx(MEMBER_1, 100 * DAY_MILLIS);
+        claim_eq!(get_join_deadline_eta(&ctx, &host), Ok(None));
+
+        // Closing joining early ends the countdown as well.
+        host.state_mut().tanda_state = TandaState::Closed;
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(get_join_deadline_eta(&ctx, &host), Ok(None));
+    }
+
+    #[concordium_test]
+    fn test_finalize_in_batches() {
+        let mut host = init_host(&default_parameter());
+        let members: Vec<(AccountAddress, u64)> =
+            (1..=12).map(|i| (AccountAddress([i as u8; 32]), i)).collect();
+        for (address, _) in members.iter() {
+            host.state_mut().deposits.insert(*address, Amount::from_micro_ccd(10));
+        }
+        host.state_mut().members = Some(members.clone());
+        host.state_mut().current_cycle = 2;
+        host.set_self_balance(Amount::from_micro_ccd(144));
+
+        let mut ctx = receive_ctx(MEMBER_1, 100 * DAY_MILLIS);
+        claim_eq!(finalize_complete(&ctx, &mut host), Err(Error::RefundsPending));
+
+        let parameter_bytes = to_bytes(&0u64);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(finalize_refund_batch(&ctx, &mut host), Ok(Some(10)));
+        claim_eq!(host.state().deposits.len(), 2);
+        claim_eq!(finalize_complete(&ctx, &mut host), Err(Error::RefundsPending));
+
+        let parameter_bytes = to_bytes(&10u64);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(finalize_refund_batch(&ctx, &mut host), Ok(None));
+        claim!(host.state().deposits.is_empty());
+
+        // The remaining 24 micro CCD are shared equally.
+        claim_eq!(finalize_complete(&ctx, &mut host), Ok(()));
+        claim!(host.state().finalized);
+        for (address, _) in members.iter() {
+            claim!(host.transfer_occurred(address, Amount::from_micro_ccd(10)));
+            claim!(host.transfer_occurred(address, Amount::from_micro_ccd(2)));
+        }
+        claim_eq!(finalize_complete(&ctx, &mut host), Err(Error::AlreadyFinalized));
+    }
+
+    #[concordium_test]
+    fn test_compact_members_after_removal() {
+        let member_3 = AccountAddress([3u8; 32]);
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2), (member_3, 3)]);
+        host.state_mut().user_index = 3;
+        host.state_mut().withdrawal_phase_started = true;
+        host.state_mut().withdrawn_addresses.insert(MEMBER_1);
+        host.state_mut().completed_cycles.push((0, vec![MEMBER_1]));
+        host.state_mut().next_receiver = Some(MEMBER_2);
+
+        let ctx = receive_ctx(MEMBER_2, DAY_MILLIS);
+        claim_eq!(forfeit(&ctx, &mut host), Ok(()));
+
+        claim_eq!(host.state().members, Some(vec![(MEMBER_1, 1), (member_3, 2)]));
+        claim_eq!(host.state().user_index, 2);
+        claim_eq!(host.state().next_receiver, Some(member_3));
+        claim_eq!(rotation_order(host.state()), vec![MEMBER_1, member_3]);
+    }
+
+    /// Sets up a club of three members who each paid a deposit of 10 and
+    /// contributed 100 for the first cycle.
+    fn abort_vote_host() -> TestHost<State> {
+        let member_3 = AccountAddress([3u8; 32]);
+        let mut host = init_host(&default_parameter());
+        let members = vec![(MEMBER_1, 1), (MEMBER_2, 2), (member_3, 3)];
+        for (address, _) in members.iter() {
+            host.state_mut().deposits.insert(*address, Amount::from_micro_ccd(10));
+            host.state_mut()
+                .contributed_cycles
+                .insert(*address, BTreeSet::from([0]));
+        }
+        host.state_mut().members = Some(members);
+        host.state_mut().total_contributions = Amount::from_micro_ccd(300);
+        host.set_self_balance(Amount::from_micro_ccd(330));
+        host
+    }
+
+    #[concordium_test]
+    fn test_vote_abort_below_threshold() {
+        let mut host = abort_vote_host();
+
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        claim_eq!(vote_abort(&ctx, &mut host), Ok(()));
+        claim_eq!(vote_abort(&ctx, &mut host), Err(Error::AlreadyVoted));
+        claim!(!host.state().aborted);
+        claim!(host.state().withdrawable_balances.is_empty());
+    }
+
+    #[concordium_test]
+    fn test_vote_abort_reaches_threshold() {
+        let mut host = abort_vote_host();
+
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        claim_eq!(vote_abort(&ctx, &mut host), Ok(()));
+        let ctx = receive_ctx(MEMBER_2, DAY_MILLIS);
+        claim_eq!(vote_abort(&ctx, &mut host), Ok(()));
+
+        claim!(host.state().aborted);
+        claim!(host.state().finalized);
+        claim_eq!(host.state().tanda_state, TandaState::Completed);
+        claim_eq!(host.state().withdrawable_balances.len(), 3);
+        for refund in host.state().withdrawable_balances.values() {
+            claim_eq!(*refund, Amount::from_micro_ccd(110));
+        }
+        claim!(host.state().deposits.is_empty());
+
+        claim_eq!(claim_payout(&ctx, &mut host), Ok(()));
+        claim!(host.transfer_occurred(&MEMBER_2, Amount::from_micro_ccd(110)));
+    }
+
+    #[concordium_test]
+    fn test_get_abort_vote_status() {
+        let mut host = abort_vote_host();
+        host.state_mut().abort_supermajority_bps = BPS_DENOMINATOR;
+
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        claim_eq!(vote_abort(&ctx, &mut host), Ok(()));
+        let ctx = receive_ctx(MEMBER_2, DAY_MILLIS);
+        claim_eq!(vote_abort(&ctx, &mut host), Ok(()));
+
+        claim_eq!(
+            get_abort_vote_status(&ctx, &host),
+            Ok(VoteStatus {
+                votes: 2,
+                threshold: 3,
+                passed: false,
+            })
+        );
+    }
+
+    #[concordium_test]
+    fn test_set_contribution_amount() {
+        let mut host = init_host(&default_parameter());
+
+        let parameter_bytes = to_bytes(&Amount::from_micro_ccd(200));
+        let mut ctx = receive_ctx(CREATOR, 0);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(set_contribution_amount(&ctx, &mut host), Ok(()));
+        claim_eq!(host.state().contribution_amount, Amount::from_micro_ccd(200));
+    }
+
+    #[concordium_test]
+    fn test_set_contribution_amount_after_start() {
+        let mut host = contributed_host();
+
+        let parameter_bytes = to_bytes(&Amount::from_micro_ccd(200));
+        let mut ctx = receive_ctx(CREATOR, DAY_MILLIS);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            set_contribution_amount(&ctx, &mut host),
+            Err(Error::AlreadyStarted)
+        );
+
+        host.state_mut().tanda_state = TandaState::Pending;
+        host.state_mut().contributors.clear();
+        claim_eq!(
+            set_contribution_amount(&ctx, &mut host),
+            Err(Error::AlreadyStarted)
+        );
+        claim_eq!(host.state().contribution_amount, Amount::from_micro_ccd(100));
+    }
+
+    #[concordium_test]
+    fn test_preview_finalize_remainder() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        host.state_mut().deposits.insert(MEMBER_1, Amount::from_micro_ccd(10));
+        host.state_mut().current_cycle = 2;
+        host.set_self_balance(Amount::from_micro_ccd(111));
+
+        let ctx = receive_ctx(MEMBER_1, 100 * DAY_MILLIS);
+        let preview = preview_finalize_remainder(&ctx, &host);
+        claim_eq!(preview, Ok(Amount::from_micro_ccd(101)));
+
+        claim_eq!(finalize(&ctx, &mut host), Ok(()));
+        let transferred = host
+            .get_transfers()
+            .iter()
+            .fold(Amount::zero(), |total, (_, amount)| total + *amount);
+        claim_eq!(preview, Ok(transferred));
+    }
+
+    #[concordium_test]
+    fn test_max_payout_per_cycle() {
+        let member_3 = AccountAddress([3u8; 32]);
+        let mut parameter = default_parameter();
+        parameter.max_payout_per_cycle = Some(Amount::from_micro_ccd(100));
+        parameter.payout_cycle = 3;
+        parameter.max_contributors = 3;
+        let mut host = init_host(&parameter);
+        let members = vec![(MEMBER_1, 1), (MEMBER_2, 2), (member_3, 3)];
+        host.state_mut().members = Some(members.clone());
+        host.state_mut().total_contributions = Amount::from_micro_ccd(450);
+
+        // Every equal share is above the cap, so the excess rolls forward.
+        let expected = [100, 100, 100];
+        for (cycle, (address, _)) in members.iter().enumerate() {
+            let now = Timestamp::from_timestamp_millis(cycle as u64 * 14 * DAY_MILLIS);
+            claim_eq!(pay_out(host.state_mut(), *address, cycle as u64, now), Ok(()));
+            claim_eq!(
+                host.state().last_payout_amount,
+                Amount::from_micro_ccd(expected[cycle])
+            );
+        }
+        claim_eq!(host.state().total_paid_out, Amount::from_micro_ccd(300));
+        claim_eq!(host.state().unclaimed_total(), Amount::from_micro_ccd(300));
+        claim_eq!(host.state().distributable_pot(), Amount::from_micro_ccd(150));
+    }
+
+    #[concordium_test]
+    fn test_withdrawal_start_time_after_contribution_phase() {
+        let mut parameter = default_parameter();
+        parameter.start_time = Timestamp::from_timestamp_millis(DAY_MILLIS);
+        let mut host = init_host(&parameter);
+        claim_eq!(
+            host.state().withdrawal_start_time,
+            Timestamp::from_timestamp_millis(29 * DAY_MILLIS)
+        );
+
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        host.state_mut().max_contributors = 1;
+        host.state_mut().contributors.insert(MEMBER_1);
+        let mut logger = TestLogger::init();
+        let ctx = receive_ctx(CREATOR, 15 * DAY_MILLIS);
+        claim_eq!(
+            start_withdrawal_phase(&ctx, &mut host, &mut logger),
+            Err(Error::WithdrawalIntervalNotReached)
+        );
+    }
+
+    #[concordium_test]
+    fn test_get_capabilities() {
+        let mut parameter = default_parameter();
+        parameter.allow_prepay = true;
+        parameter.sub_round_size = Some(2);
+        parameter.late_join_multiplier = 1;
+        let host = init_host(&parameter);
+
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(
+            get_capabilities(&ctx, &host),
+            Ok(Capabilities {
+                prepay: true,
+                cosign: false,
+                join_fee: false,
+                late_join_multiplier: false,
+                sub_rounds: true,
+                payout_cap: false,
+                backup_finalizer: false,
+                reminders: true,
+            })
+        );
+    }
+
+    #[concordium_test]
+    fn test_rolling_deadlines_from_join() {
+        let mut parameter = default_parameter();
+        parameter.deadline_mode = DeadlineMode::RollingFromJoin;
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        host.state_mut().joined_at.insert(MEMBER_1, Timestamp::from_timestamp_millis(0));
+        host.state_mut()
+            .joined_at
+            .insert(MEMBER_2, Timestamp::from_timestamp_millis(5 * DAY_MILLIS));
+
+        let mut ctx = receive_ctx(MEMBER_1, 6 * DAY_MILLIS);
+        let parameter_bytes = to_bytes(&MEMBER_1);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            get_member_deadline(&ctx, &host),
+            Ok(Some(Timestamp::from_timestamp_millis(14 * DAY_MILLIS)))
+        );
+        let parameter_bytes = to_bytes(&MEMBER_2);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            get_member_deadline(&ctx, &host),
+            Ok(Some(Timestamp::from_timestamp_millis(19 * DAY_MILLIS)))
+        );
+
+        // At day 15 MEMBER_1 is in its second cycle, MEMBER_2 still in its first.
+        let mut logger = TestLogger::init();
+        let ctx = receive_ctx(MEMBER_2, 15 * DAY_MILLIS);
+        claim_eq!(
+            contribute(&ctx, &mut host, Amount::from_micro_ccd(100), &mut logger),
+            Ok(())
+        );
+        claim!(host.state().has_contributed(&MEMBER_2, 0));
+    }
+
+    #[concordium_test]
+    fn test_get_cycle_shortfall() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        host.state_mut().total_contributions = Amount::from_micro_ccd(100);
+
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        claim_eq!(get_cycle_shortfall(&ctx, &host), Ok(Amount::from_micro_ccd(100)));
+
+        host.state_mut().total_contributions = Amount::from_micro_ccd(200);
+        claim_eq!(get_cycle_shortfall(&ctx, &host), Ok(Amount::zero()));
+    }
+
+    #[concordium_test]
+    fn test_defer_payout() {
+        let member_3 = AccountAddress([3u8; 32]);
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2), (member_3, 3)]);
+        host.state_mut().user_index = 3;
+        host.state_mut().next_receiver = Some(MEMBER_1);
+
+        let ctx = receive_ctx(MEMBER_2, DAY_MILLIS);
+        claim_eq!(defer_payout(&ctx, &mut host), Err(Error::NotAuthorized));
+
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        claim_eq!(defer_payout(&ctx, &mut host), Ok(()));
+        claim_eq!(host.state().next_receiver, Some(MEMBER_2));
+        claim_eq!(rotation_order(host.state()), vec![MEMBER_2, member_3, MEMBER_1]);
+        claim_eq!(
+            host.state().members,
+            Some(vec![(MEMBER_2, 2), (member_3, 3), (MEMBER_1, 4)])
+        );
+        claim!(host.state().members_consistent());
+    }
+
+    #[concordium_test]
+    fn test_defer_payout_limit() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        host.state_mut().user_index = 2;
+        host.state_mut().next_receiver = Some(MEMBER_1);
+
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        claim_eq!(defer_payout(&ctx, &mut host), Ok(()));
+        let ctx = receive_ctx(MEMBER_2, DAY_MILLIS);
+        claim_eq!(defer_payout(&ctx, &mut host), Ok(()));
+
+        // MEMBER_1 is next in line again but cannot defer a second time.
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        claim_eq!(host.state().next_receiver, Some(MEMBER_1));
+        claim_eq!(defer_payout(&ctx, &mut host), Err(Error::DeferralLimitReached));
+    }
+
+    #[concordium_test]
+    fn test_broken_streak_bumped_to_later_slot() {
+        let member_3 = AccountAddress([3u8; 32]);
+        let mut parameter = default_parameter();
+        parameter.min_streak = 3;
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2), (member_3, 3)]);
+        host.state_mut().contributed_cycles.insert(MEMBER_1, BTreeSet::from([0, 2]));
+        host.state_mut().contributed_cycles.insert(MEMBER_2, BTreeSet::from([0, 1, 2]));
+        host.state_mut().contributed_cycles.insert(member_3, BTreeSet::from([0, 1, 2]));
+
+        claim_eq!(host.state().contribution_streak(&MEMBER_1), 1);
+        claim_eq!(select_next_receiver(host.state()), Some(MEMBER_2));
+        claim_eq!(rotation_order(host.state()), vec![MEMBER_2, member_3, MEMBER_1]);
+    }
+
+    #[concordium_test]
+    fn test_get_member_ledger() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        host.state_mut().deposits.insert(MEMBER_2, Amount::from_micro_ccd(10));
+        let mut logger = TestLogger::init();
+
+        // MEMBER_2 misses the first cycle and pays late for the second.
+        let ctx = receive_ctx(MEMBER_2, 14 * DAY_MILLIS);
+        claim_eq!(
+            contribute(&ctx, &mut host, Amount::from_micro_ccd(110), &mut logger),
+            Ok(())
+        );
+        claim_eq!(
+            pay_out(
+                host.state_mut(),
+                MEMBER_2,
+                0,
+                Timestamp::from_timestamp_millis(28 * DAY_MILLIS),
+            ),
+            Ok(())
+        );
+
+        let parameter_bytes = to_bytes(&MEMBER_2);
+        let mut ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            get_member_ledger(&ctx, &host),
+            Ok(MemberLedger {
+                contributed: Amount::from_micro_ccd(100),
+                penalties_owed: Amount::zero(),
+                penalties_paid: Amount::from_micro_ccd(10),
+                deposit: Amount::from_micro_ccd(10),
+                withdrawn: Amount::from_micro_ccd(55),
+            })
+        );
+
+        // MEMBER_1 missed the second cycle and owes a penalty for the third.
+        let parameter_bytes = to_bytes(&MEMBER_1);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            get_member_ledger(&ctx, &host).map(|ledger| ledger.penalties_owed),
+            Ok(Amount::from_micro_ccd(10))
+        );
+    }
+
+    #[concordium_test]
+    fn test_start_withdrawal_phase_far_future() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        host.state_mut().max_contributors = 1;
+        host.state_mut().contributors.insert(MEMBER_1);
+        let start = Timestamp::from_timestamp_millis(u64::MAX - 1);
+        host.state_mut().withdrawal_start_time = start;
+        let mut logger = TestLogger::init();
+
+        // The first payout is due when the phase opens, so nothing overflows
+        // until the payout itself schedules the next one.
+        let ctx = receive_ctx(CREATOR, u64::MAX - 1);
+        claim_eq!(start_withdrawal_phase(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(host.state().next_withdrawal_time, start);
+        claim_eq!(pay_out(host.state_mut(), MEMBER_1, 0, start), Err(Error::InvalidState));
+    }
+
+    #[concordium_test]
+    fn test_get_payout_ledger() {
+        let member_3 = AccountAddress([3u8; 32]);
+        let mut host = init_host(&default_parameter());
+        let receivers = [MEMBER_1, MEMBER_2, member_3];
+        for (cycle, receiver) in receivers.iter().enumerate() {
+            host.state_mut().total_contributions += Amount::from_micro_ccd(100);
+            let now = Timestamp::from_timestamp_millis((cycle as u64 + 2) * 14 * DAY_MILLIS);
+            claim_eq!(pay_out(host.state_mut(), *receiver, cycle as u64, now), Ok(()));
+        }
+
+        let parameter_bytes = to_bytes(&PaginationParams { offset: 0, limit: 2 });
+        let mut ctx = receive_ctx(MEMBER_1, 60 * DAY_MILLIS);
+        ctx.set_parameter(&parameter_bytes);
+        let page = get_payout_ledger(&ctx, &host).unwrap();
+        claim_eq!(page.rows.len(), 2);
+        claim_eq!(page.next_offset, Some(2));
+        claim_eq!(
+            page.rows[1],
+            PayoutRecord {
+                cycle: 1,
+                receiver: MEMBER_2,
+                amount: Amount::from_micro_ccd(100),
+                timestamp: Timestamp::from_timestamp_millis(42 * DAY_MILLIS),
+            }
+        );
+
+        let parameter_bytes = to_bytes(&PaginationParams { offset: 2, limit: 2 });
+        ctx.set_parameter(&parameter_bytes);
+        let page = get_payout_ledger(&ctx, &host).unwrap();
+        claim_eq!(page.next_offset, None);
+        claim_eq!(page.rows.len(), 1);
+        claim_eq!(page.rows[0].receiver, member_3);
+    }
+
+    /// Sets up a club of five members of whom the given number contributed,
+    /// with a payout quorum of 80%.
+    fn quorum_host(contributors: u8) -> TestHost<State> {
+        let mut parameter = default_parameter();
+        parameter.payout_quorum_bps = 8000;
+        parameter.max_contributors = 5;
+        let mut host = init_host(&parameter);
+        let members: Vec<(AccountAddress, u64)> =
+            (1..=5).map(|i| (AccountAddress([i as u8; 32]), i)).collect();
+        host.state_mut().members = Some(members);
+        for i in (6 - contributors)..=5 {
+            host.state_mut().contributors.insert(AccountAddress([i; 32]));
+        }
+        host
+    }
+
+    #[concordium_test]
+    fn test_start_withdrawal_phase_at_quorum() {
+        let mut host = quorum_host(4);
+        let mut logger = TestLogger::init();
+
+        let ctx = receive_ctx(CREATOR, 28 * DAY_MILLIS);
+        claim_eq!(start_withdrawal_phase(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(host.state().skipped_receivers, vec![MEMBER_1]);
+        claim_eq!(host.state().next_receiver, Some(MEMBER_2));
+    }
+
+    #[concordium_test]
+    fn test_start_withdrawal_phase_below_quorum() {
+        let mut host = quorum_host(3);
+        let mut logger = TestLogger::init();
+
+        let ctx = receive_ctx(CREATOR, 28 * DAY_MILLIS);
+        claim_eq!(
+            start_withdrawal_phase(&ctx, &mut host, &mut logger),
+            Err(Error::ContributorsNotComplete)
+        );
+    }
+
+    #[concordium_test]
+    fn test_is_reconciled() {
+        let mut host = contributed_host();
+
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        claim_eq!(is_reconciled(&ctx, &host), Ok(true));
+
+        host.state_mut().total_contributions = Amount::from_micro_ccd(500);
+        claim_eq!(is_reconciled(&ctx, &host), Ok(false));
+    }
+
+    #[concordium_test]
+    fn test_probation_for_late_joiner() {
+        let mut parameter = default_parameter();
+        parameter.probation_cycles = 3;
+        let member_3 = AccountAddress([3u8; 32]);
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_2, 1), (member_3, 2)]);
+        host.state_mut()
+            .joined_at
+            .insert(MEMBER_2, Timestamp::from_timestamp_millis(15 * DAY_MILLIS));
+        host.state_mut().joined_at.insert(member_3, Timestamp::from_timestamp_millis(0));
+
+        // MEMBER_2 joined in cycle 1, so is on probation until cycle 4.
+        claim!(host.state().in_probation(&MEMBER_2));
+        claim_eq!(select_next_receiver(host.state()), Some(member_3));
+
+        host.state_mut().current_cycle = 2;
+        claim!(!host.state().in_probation(&MEMBER_2));
+        claim_eq!(select_next_receiver(host.state()), Some(MEMBER_2));
+    }
+
+    #[concordium_test]
+    fn test_get_member_statuses() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        host.state_mut().deposits.insert(MEMBER_2, Amount::from_micro_ccd(10));
+        host.state_mut().withdrawn_addresses.insert(MEMBER_1);
+
+        let parameter_bytes = to_bytes(&vec![MEMBER_2, MEMBER_1]);
+        let mut ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        ctx.set_parameter(&parameter_bytes);
+        let statuses = get_member_statuses(&ctx, &host).unwrap();
+        claim_eq!(statuses.len(), 2);
+        claim_eq!(statuses[0].index, 2);
+        claim_eq!(statuses[0].deposit, Amount::from_micro_ccd(10));
+        claim!(!statuses[0].withdrawn);
+        claim_eq!(statuses[1].index, 1);
+        claim!(statuses[1].withdrawn);
+
+        let parameter_bytes = to_bytes(&vec![MEMBER_1, CREATOR]);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(get_member_statuses(&ctx, &host), Err(Error::NotJoined));
+    }
+
+    #[concordium_test]
+    fn test_withdraw_insufficient_balance() {
+        let mut host = contributed_host();
+        let mut logger = TestLogger::init();
+        let ctx = receive_ctx(CREATOR, 28 * DAY_MILLIS);
+        claim_eq!(start_withdrawal_phase(&ctx, &mut host, &mut logger), Ok(()));
+        host.set_self_balance(Amount::from_micro_ccd(50));
+
+        let ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+        claim_eq!(
+            withdraw(&ctx, &mut host, &mut logger),
+            Err(Error::InsufficientBalance)
+        );
+        claim!(host.state().withdrawable_balances.is_empty());
+        claim!(host.state().withdrawn_addresses.is_empty());
+        claim_eq!(host.state().tanda_state, TandaState::Pending);
+    }
+
+    #[concordium_test]
+    fn test_claim_insufficient_balance() {
+        let mut host = paid_out_host();
+        host.set_self_balance(Amount::from_micro_ccd(50));
+
+        let ctx = receive_ctx(MEMBER_1, 29 * DAY_MILLIS);
+        claim_eq!(claim_payout(&ctx, &mut host), Err(Error::InsufficientBalance));
+        claim_eq!(
+            host.state().withdrawable_balances.get(&MEMBER_1),
+            Some(&Amount::from_micro_ccd(100))
+        );
+        claim!(host.get_transfers().is_empty());
+    }
+
+    #[concordium_test]
+    fn test_get_my_next_deadline_global() {
+        let mut parameter = default_parameter();
+        parameter.payout_cycle = 4;
+        parameter.max_contributors = 4;
+        parameter.end_time = Timestamp::from_timestamp_millis(112 * DAY_MILLIS);
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        host.state_mut().contributed_cycles.insert(MEMBER_1, BTreeSet::from([0, 1]));
+
+        // MEMBER_1 prepaid the second cycle, so its next deadline is the third's.
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        claim_eq!(
+            get_my_next_deadline(&ctx, &host),
+            Ok(Timestamp::from_timestamp_millis(42 * DAY_MILLIS))
+        );
+        let ctx = receive_ctx(MEMBER_2, DAY_MILLIS);
+        claim_eq!(
+            get_my_next_deadline(&ctx, &host),
+            Ok(Timestamp::from_timestamp_millis(14 * DAY_MILLIS))
+        );
+    }
+
+    #[concordium_test]
+    fn test_get_my_next_deadline_rolling() {
+        let mut parameter = default_parameter();
+        parameter.deadline_mode = DeadlineMode::RollingFromJoin;
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        host.state_mut()
+            .joined_at
+            .insert(MEMBER_2, Timestamp::from_timestamp_millis(3 * DAY_MILLIS));
+
+        let ctx = receive_ctx(MEMBER_1, 4 * DAY_MILLIS);
+        claim_eq!(
+            get_my_next_deadline(&ctx, &host),
+            Ok(Timestamp::from_timestamp_millis(14 * DAY_MILLIS))
+        );
+        let ctx = receive_ctx(MEMBER_2, 4 * DAY_MILLIS);
+        claim_eq!(
+            get_my_next_deadline(&ctx, &host),
+            Ok(Timestamp::from_timestamp_millis(17 * DAY_MILLIS))
+        );
+    }
+
+    #[concordium_test]
+    fn test_join_waitlist() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+
+        // The club still has a free slot.
+        let ctx = receive_ctx(BACKUP, DAY_MILLIS);
+        claim_eq!(
+            join_waitlist(&ctx, &mut host, Amount::from_micro_ccd(10)),
+            Err(Error::ClubNotFull)
+        );
+
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        claim_eq!(join_waitlist(&ctx, &mut host, Amount::from_micro_ccd(10)), Ok(()));
+        claim_eq!(
+            join_waitlist(&ctx, &mut host, Amount::from_micro_ccd(10)),
+            Err(Error::AlreadyJoined)
+        );
+        claim_eq!(host.state().waitlist, vec![(BACKUP, Amount::from_micro_ccd(10))]);
+    }
+
+    #[concordium_test]
+    fn test_waitlist_promotion_on_vacancy() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        host.state_mut().user_index = 2;
+        host.state_mut().deposits.insert(MEMBER_1, Amount::from_micro_ccd(10));
+        host.state_mut().deposits.insert(MEMBER_2, Amount::from_micro_ccd(10));
+        host.state_mut().waitlist.push((BACKUP, Amount::from_micro_ccd(10)));
+        host.set_self_balance(Amount::from_micro_ccd(30));
+
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        claim_eq!(forfeit(&ctx, &mut host), Ok(()));
+
+        claim!(host.state().waitlist.is_empty());
+        claim_eq!(host.state().members, Some(vec![(BACKUP, 1), (MEMBER_2, 2)]));
+        claim_eq!(host.state().deposits.get(&BACKUP), Some(&Amount::from_micro_ccd(10)));
+        claim_eq!(select_next_receiver(host.state()), Some(BACKUP));
+    }
+
+    #[concordium_test]
+    fn test_get_club_compliance() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        host.state_mut().contributed_cycles.insert(MEMBER_1, BTreeSet::from([0, 1]));
+        host.state_mut().contributed_cycles.insert(MEMBER_2, BTreeSet::from([0, 1]));
+
+        let ctx = receive_ctx(MEMBER_1, 30 * DAY_MILLIS);
+        claim_eq!(get_club_compliance(&ctx, &host), Ok(100));
+
+        // MEMBER_2 defaulted on the second cycle: (100 + 50) / 2.
+        host.state_mut().contributed_cycles.insert(MEMBER_2, BTreeSet::from([0]));
+        claim_eq!(get_club_compliance(&ctx, &host), Ok(75));
+    }
+
+    #[concordium_test]
+    fn test_escalating_late_penalty() {
+        let mut parameter = default_parameter();
+        parameter.escalation_bps = 5000;
+        parameter.payout_cycle = 4;
+        parameter.max_contributors = 4;
+        parameter.end_time = Timestamp::from_timestamp_millis(112 * DAY_MILLIS);
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        let mut logger = TestLogger::init();
+
+        // First default: the base penalty of 10.
+        let ctx = receive_ctx(MEMBER_1, 14 * DAY_MILLIS);
+        claim_eq!(
+            contribute(&ctx, &mut host, Amount::from_micro_ccd(110), &mut logger),
+            Ok(())
+        );
+
+        // Second default: 50% more.
+        let ctx = receive_ctx(MEMBER_1, 42 * DAY_MILLIS);
+        claim_eq!(
+            contribute(&ctx, &mut host, Amount::from_micro_ccd(110), &mut logger),
+            Err(Error::Penalized)
+        );
+        claim_eq!(
+            contribute(&ctx, &mut host, Amount::from_micro_ccd(115), &mut logger),
+            Ok(())
+        );
+        claim_eq!(host.state().penalties_paid.get(&MEMBER_1), Some(&Amount::from_micro_ccd(25)));
+    }
+
+    #[concordium_test]
+    fn test_get_slot_assignments() {
+        let member_3 = AccountAddress([3u8; 32]);
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2), (member_3, 3)]);
+        host.state_mut().payout_cycle = 3;
+        host.state_mut().total_contributions = Amount::from_micro_ccd(300);
+        claim_eq!(
+            pay_out(
+                host.state_mut(),
+                MEMBER_1,
+                0,
+                Timestamp::from_timestamp_millis(42 * DAY_MILLIS),
+            ),
+            Ok(())
+        );
+
+        let ctx = receive_ctx(MEMBER_1, 43 * DAY_MILLIS);
+        let assignments = get_slot_assignments(&ctx, &host).unwrap();
+        claim_eq!(assignments, vec![(MEMBER_1, 0), (MEMBER_2, 1), (member_3, 2)]);
+        let mut cycles: Vec<u64> = assignments.iter().map(|(_, cycle)| *cycle).collect();
+        cycles.sort_unstable();
+        claim_eq!(cycles, (0..3).collect::<Vec<u64>>());
+    }
+
+    #[concordium_test]
+    fn test_frozen_rotation_order() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        host.state_mut().contributors.insert(MEMBER_1);
+        host.state_mut().contributors.insert(MEMBER_2);
+        let mut logger = TestLogger::init();
+
+        let ctx = receive_ctx(CREATOR, 28 * DAY_MILLIS);
+        claim_eq!(start_withdrawal_phase(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(host.state().frozen_order, vec![MEMBER_1, MEMBER_2]);
+
+        // Renumbering the members after the freeze does not change the order.
+        host.state_mut().members = Some(vec![(MEMBER_2, 1), (MEMBER_1, 2)]);
+        claim_eq!(select_next_receiver(host.state()), Some(MEMBER_1));
+        claim_eq!(rotation_order(host.state()), vec![MEMBER_1, MEMBER_2]);
+    }
+
+    #[concordium_test]
+    fn test_init_fewer_members_than_cycles() {
+        let mut parameter = default_parameter();
+        parameter.payout_cycle = 3;
+        claim_eq!(
+            try_init(&parameter).map(|_| ()),
+            Err(Reject::from(Error::InvalidPayoutCycle))
+        );
+
+        // One member per cycle is the boundary that is still accepted.
+        parameter.max_contributors = 3;
+        claim!(try_init(&parameter).is_ok());
+    }
+
+    #[concordium_test]
+    fn test_init_zero_payout_cycles() {
+        let mut parameter = default_parameter();
+        parameter.payout_cycle = 0;
+        claim_eq!(
+            try_init(&parameter).map(|_| ()),
+            Err(Reject::from(Error::InvalidPayoutCycle))
+        );
+    }
+
+    #[concordium_test]
+    fn test_init_end_time_before_last_payout() {
+        // Two contribution cycles and two payouts of 14 days each take 56 days.
+        let mut parameter = default_parameter();
+        parameter.end_time = Timestamp::from_timestamp_millis(56 * DAY_MILLIS - 1);
+        claim_eq!(try_init(&parameter).map(|_| ()), Err(Reject::from(Error::InvalidEndTime)));
+
+        parameter.end_time = Timestamp::from_timestamp_millis(56 * DAY_MILLIS);
+        claim!(try_init(&parameter).is_ok());
+    }
+
+    #[concordium_test]
+    fn test_get_metrics() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().max_contributors = 1;
+        let mut logger = TestLogger::init();
+
+        let parameter_bytes = to_bytes(&JoinTandaParameter { penalty_amount: 10 });
+        let mut ctx = receive_ctx(MEMBER_1, 0);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            join_tanda(&ctx, &mut host, Amount::from_micro_ccd(10), &mut logger),
+            Ok(())
+        );
+
+        // Paying only in the second cycle costs the late penalty.
+        let ctx = receive_ctx(MEMBER_1, 14 * DAY_MILLIS);
+        claim_eq!(
+            contribute(&ctx, &mut host, Amount::from_micro_ccd(110), &mut logger),
+            Ok(())
+        );
+        host.set_self_balance(Amount::from_micro_ccd(120));
+
+        let ctx = receive_ctx(CREATOR, 28 * DAY_MILLIS);
+        claim_eq!(start_withdrawal_phase(&ctx, &mut host, &mut logger), Ok(()));
+        let ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+        claim_eq!(withdraw(&ctx, &mut host, &mut logger), Ok(()));
+
+        claim_eq!(
+            get_metrics(&ctx, &host),
+            Ok(Metrics {
+                joins: 1,
+                contributions: 1,
+                payouts: 1,
+                penalties_collected: Amount::from_micro_ccd(10),
+                balance: Amount::from_micro_ccd(120),
+            })
+        );
+    }
+
+    #[concordium_test]
+    fn test_contribute_before_and_after_start() {
+        let mut parameter = default_parameter();
+        parameter.start_time = Timestamp::from_timestamp_millis(DAY_MILLIS);
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        let mut logger = TestLogger::init();
+        let amount = Amount::from_micro_ccd(100);
+
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(contribute(&ctx, &mut host, amount, &mut logger), Err(Error::NotStarted));
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        claim_eq!(contribute(&ctx, &mut host, amount, &mut logger), Ok(()));
+
+        // Once the payouts are in progress the contribution phase is over.
+        host.state_mut().tanda_state = TandaState::InProgress;
+        let ctx = receive_ctx(MEMBER_2, DAY_MILLIS);
+        claim_eq!(contribute(&ctx, &mut host, amount, &mut logger), Err(Error::TandaClosed));
+    }
+
+    #[concordium_test]
+    fn test_set_penalty_amount() {
+        let mut host = init_host(&default_parameter());
+        let parameter_bytes = to_bytes(&Amount::from_micro_ccd(20));
+
+        let mut ctx = receive_ctx(MEMBER_1, 0);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(set_penalty_amount(&ctx, &mut host), Err(Error::NotAuthorized));
+
+        let mut ctx = receive_ctx(CREATOR, 0);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(set_penalty_amount(&ctx, &mut host), Ok(()));
+        claim_eq!(host.state().penalty_amount, Amount::from_micro_ccd(20));
+
+        // The early bird deposit cannot end up above the penalty amount.
+        host.state_mut().early_bird_count = 1;
+        host.state_mut().early_bird_penalty = Amount::from_micro_ccd(5);
+        let parameter_bytes = to_bytes(&Amount::from_micro_ccd(4));
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(set_penalty_amount(&ctx, &mut host), Err(Error::InvalidPenaltyAmount));
+        claim_eq!(host.state().penalty_amount, Amount::from_micro_ccd(20));
+
+        // Deposits already paid would no longer match the new amount.
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        claim_eq!(set_penalty_amount(&ctx, &mut host), Err(Error::AlreadyStarted));
+    }
+
+    #[concordium_test]
+    fn test_get_my_outstanding_cycles() {
+        let mut parameter = default_parameter();
+        parameter.payout_cycle = 4;
+        parameter.max_contributors = 4;
+        parameter.end_time = Timestamp::from_timestamp_millis(112 * DAY_MILLIS);
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        host.state_mut().contributed_cycles.insert(MEMBER_1, BTreeSet::from([0, 2]));
+
+        let ctx = receive_ctx(MEMBER_1, 45 * DAY_MILLIS);
+        claim_eq!(get_my_outstanding_cycles(&ctx, &host), Ok(vec![1]));
+        let ctx = receive_ctx(MEMBER_2, 45 * DAY_MILLIS);
+        claim_eq!(get_my_outstanding_cycles(&ctx, &host), Err(Error::NotJoined));
+    }
+
+    #[concordium_test]
+    fn test_settle_incomplete() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        let state = host.state_mut();
+        for member in [MEMBER_1, MEMBER_2] {
+            state.deposits.insert(member, Amount::from_micro_ccd(10));
+            state.member_contributions.insert(member, Amount::from_micro_ccd(200));
+            state.contributors.insert(member);
+        }
+        state.total_contributions = Amount::from_micro_ccd(400);
+
+        // The first payout was made and claimed, then the club timed out.
+        let now = Timestamp::from_timestamp_millis(28 * DAY_MILLIS);
+        claim_eq!(pay_out(host.state_mut(), MEMBER_1, 0, now), Ok(()));
+        host.state_mut().withdrawable_balances.clear();
+        host.set_self_balance(Amount::from_micro_ccd(220));
+
+        let ctx = receive_ctx(MEMBER_2, 100 * DAY_MILLIS);
+        claim_eq!(settle_incomplete(&ctx, &mut host), Err(Error::EndTimeNotReached));
+
+        // The first receiver got their contributions back and is owed their
+        // deposit; the member who was never paid out gets all they paid in.
+        let ctx = receive_ctx(MEMBER_2, 100 * DAY_MILLIS + 1);
+        claim_eq!(settle_incomplete(&ctx, &mut host), Ok(()));
+        claim!(host.state().settled);
+        claim_eq!(host.state().tanda_state, TandaState::Completed);
+        claim_eq!(
+            host.state().withdrawable_balances.get(&MEMBER_1),
+            Some(&Amount::from_micro_ccd(10))
+        );
+        claim_eq!(
+            host.state().withdrawable_balances.get(&MEMBER_2),
+            Some(&Amount::from_micro_ccd(210))
+        );
+        claim_eq!(settle_incomplete(&ctx, &mut host), Err(Error::AlreadyFinalized));
+    }
+
+    #[concordium_test]
+    fn test_get_available_actions() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        let actions_at = |host: &TestHost<State>, account: AccountAddress, millis: u64| {
+            let parameter_bytes = to_bytes(&account);
+            let mut ctx = receive_ctx(account, millis);
+            ctx.set_parameter(&parameter_bytes);
+            get_available_actions(&ctx, host)
+        };
+
+        claim_eq!(actions_at(&host, MEMBER_2, 0), Ok(vec![ActionKind::Join]));
+        claim_eq!(actions_at(&host, MEMBER_1, 0), Ok(vec![ActionKind::Contribute]));
+        claim_eq!(actions_at(&host, CREATOR, 0), Ok(vec![ActionKind::Join]));
+
+        // Once the club is full and everybody paid, the creator can start
+        // the payouts.
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        host.state_mut().contributors.insert(MEMBER_1);
+        host.state_mut().contributors.insert(MEMBER_2);
+        claim_eq!(actions_at(&host, CREATOR, 0), Ok(vec![]));
+        claim_eq!(
+            actions_at(&host, CREATOR, 28 * DAY_MILLIS),
+            Ok(vec![ActionKind::StartPayouts])
+        );
+    }
+
+    #[concordium_test]
+    fn test_equal_share() {
+        claim_eq!(
+            equal_share(Amount::from_micro_ccd(300), 3),
+            Some(Amount::from_micro_ccd(100))
+        );
+        claim_eq!(
+            equal_share(Amount::from_micro_ccd(301), 3),
+            Some(Amount::from_micro_ccd(100))
+        );
+        claim_eq!(equal_share(Amount::from_micro_ccd(300), 0), None);
+    }
+
+    #[concordium_test]
+    fn test_per_cycle_share_exact() {
+        claim_eq!(
+            per_cycle_share(Amount::from_micro_ccd(400), 2),
+            Ok(Amount::from_micro_ccd(200))
+        );
+    }
+
+    #[concordium_test]
+    fn test_per_cycle_share_truncates_remainder() {
+        claim_eq!(
+            per_cycle_share(Amount::from_micro_ccd(401), 3),
+            Ok(Amount::from_micro_ccd(133))
+        );
+    }
+
+    #[concordium_test]
+    fn test_per_cycle_share_zero_cycles() {
+        claim_eq!(
+            per_cycle_share(Amount::from_micro_ccd(400), 0),
+            Err(Error::InvalidPayoutCycle)
+        );
+    }
+
+    #[concordium_test]
+    fn test_terms_locked_on_first_contribution() {
+        let mut parameter = default_parameter();
+        parameter.lock_terms = true;
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        let mut logger = TestLogger::init();
+
+        let parameter_bytes = to_bytes(&String::from("Savings"));
+        let mut ctx = receive_ctx(CREATOR, 0);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(rename(&ctx, &mut host, &mut logger), Ok(()));
+
+        let member_ctx = receive_ctx(MEMBER_1, 0);
+        let result = contribute(&member_ctx, &mut host, Amount::from_micro_ccd(100), &mut logger);
+        claim_eq!(result, Ok(()));
+        claim!(host.state().terms_locked);
+        claim_eq!(rename(&ctx, &mut host, &mut logger), Err(Error::AlreadyStarted));
+
+        let parameter_bytes = to_bytes(&Amount::from_micro_ccd(200));
+        let mut ctx = receive_ctx(CREATOR, 0);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(set_contribution_amount(&ctx, &mut host), Err(Error::AlreadyStarted));
+        claim_eq!(set_penalty_amount(&ctx, &mut host), Err(Error::AlreadyStarted));
+    }
+
+    #[concordium_test]
+    fn test_get_my_last_contribution_time() {
+        let host = contributed_host();
+
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        claim_eq!(
+            get_my_last_contribution_time(&ctx, &host),
+            Ok(Some(Timestamp::from_timestamp_millis(0)))
+        );
+        let ctx = receive_ctx(MEMBER_2, DAY_MILLIS);
+        claim_eq!(get_my_last_contribution_time(&ctx, &host), Ok(None));
+    }
+
+    #[concordium_test]
+    fn test_get_rotation_position() {
+        let member_3 = AccountAddress([3u8; 32]);
+        let mut parameter = default_parameter();
+        parameter.max_contributors = 3;
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2), (member_3, 3)]);
+        claim_eq!(forfeit(&receive_ctx(MEMBER_2, 0), &mut host), Ok(()));
+
+        let position_of = |host: &TestHost<State>, account: AccountAddress| {
+            let parameter_bytes = to_bytes(&account);
+            let mut ctx = receive_ctx(MEMBER_1, 0);
+            ctx.set_parameter(&parameter_bytes);
+            get_rotation_position(&ctx, host)
+        };
+        claim_eq!(position_of(&host, member_3), Ok(Some(1)));
+        claim_eq!(position_of(&host, MEMBER_2), Ok(None));
+        claim_eq!(position_of(&host, BACKUP), Ok(None));
+    }
+
+    #[concordium_test]
+    fn test_final_cycle_tolerance() {
+        let mut parameter = default_parameter();
+        parameter.final_cycle_tolerance = Amount::from_micro_ccd(5);
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        host.state_mut().deposits.insert(MEMBER_1, Amount::from_micro_ccd(10));
+        host.state_mut().contributed_cycles.insert(MEMBER_1, BTreeSet::from([0]));
+        let mut logger = TestLogger::init();
+
+        // Short by more than the tolerance.
+        let ctx = receive_ctx(MEMBER_1, 14 * DAY_MILLIS);
+        claim_eq!(
+            contribute(&ctx, &mut host, Amount::from_micro_ccd(94), &mut logger),
+            Err(Error::InvalidContributionAmount)
+        );
+
+        claim_eq!(
+            contribute(&ctx, &mut host, Amount::from_micro_ccd(97), &mut logger),
+            Ok(())
+        );
+        claim!(host.state().has_contributed(&MEMBER_1, 1));
+        claim_eq!(host.state().deposits.get(&MEMBER_1), Some(&Amount::from_micro_ccd(7)));
+        claim_eq!(host.state().total_contributions, Amount::from_micro_ccd(100));
+    }
+
+    #[concordium_test]
+    fn test_can_finalize() {
+        let mut host = paid_out_host();
+
+        let ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+        claim_eq!(can_finalize(&ctx, &host), Ok(FinalizeEligibility::NotStarted));
+
+        host.state_mut().current_cycle = 2;
+        claim_eq!(can_finalize(&ctx, &host), Ok(FinalizeEligibility::Ok));
+
+        host.state_mut().finalized = true;
+        claim_eq!(can_finalize(&ctx, &host), Ok(FinalizeEligibility::AlreadyFinalized));
+    }
+
+    #[concordium_test]
+    fn test_full_rotation_from_test_default() {
+        let state = State::test_default(&[MEMBER_1, MEMBER_2]);
+        let mut host = TestHost::new(state, TestStateBuilder::new());
+        let mut logger = TestLogger::init();
+
+        let contributions = [
+            (0, MEMBER_1),
+            (0, MEMBER_2),
+            (14 * DAY_MILLIS, MEMBER_1),
+            (14 * DAY_MILLIS, MEMBER_2),
+        ];
+        for (millis, member) in contributions {
+            let ctx = receive_ctx(member, millis);
+            let result = contribute(&ctx, &mut host, Amount::from_micro_ccd(100), &mut logger);
+            claim_eq!(result, Ok(()));
+        }
+        host.set_self_balance(Amount::from_micro_ccd(400));
+
+        let ctx = receive_ctx(CREATOR, 28 * DAY_MILLIS);
+        claim_eq!(start_withdrawal_phase(&ctx, &mut host, &mut logger), Ok(()));
+        let ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+        claim_eq!(withdraw(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(
+            host.state().withdrawable_balances.get(&MEMBER_1),
+            Some(&Amount::from_micro_ccd(200))
+        );
+        let ctx = receive_ctx(MEMBER_2, 42 * DAY_MILLIS);
+        claim_eq!(withdraw(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(
+            host.state().withdrawable_balances.get(&MEMBER_2),
+            Some(&Amount::from_micro_ccd(200))
+        );
+
+        let ctx = receive_ctx(MEMBER_1, 56 * DAY_MILLIS);
+        claim_eq!(finalize(&ctx, &mut host), Ok(()));
+        claim_eq!(host.state().completed_cycles.len(), 2);
+        claim_eq!(host.state().tanda_state, TandaState::Completed);
+    }
+
+    #[concordium_test]
+    fn test_join_rejects_duplicate_index() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        // A corrupted counter would hand out index 1 again.
+        host.state_mut().user_index = 0;
+        let mut logger = TestLogger::init();
+
+        let parameter_bytes = to_bytes(&JoinTandaParameter { penalty_amount: 10 });
+        let mut ctx = receive_ctx(MEMBER_2, 0);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            join_tanda(&ctx, &mut host, Amount::from_micro_ccd(10), &mut logger),
+            Err(Error::InternalError)
+        );
+        claim_eq!(host.state().members, Some(vec![(MEMBER_1, 1)]));
+        claim!(!validate_member_indices(&[(MEMBER_1, 1), (MEMBER_2, 1)]));
+        claim!(validate_member_indices(&[(MEMBER_1, 1), (MEMBER_2, 2)]));
+    }
+
+    #[concordium_test]
+    fn test_get_contribution_phase() {
+        let mut parameter = default_parameter();
+        parameter.start_time = Timestamp::from_timestamp_millis(DAY_MILLIS);
+        let host = init_host(&parameter);
+
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(
+            get_contribution_phase(&ctx, &host),
+            Ok((
+                Timestamp::from_timestamp_millis(DAY_MILLIS),
+                Timestamp::from_timestamp_millis(29 * DAY_MILLIS)
+            ))
+        );
+    }
+
+    #[concordium_test]
+    fn test_auto_penalize_missed_cycle() {
+        let mut parameter = default_parameter();
+        parameter.auto_penalize = true;
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        host.state_mut().deposits.insert(MEMBER_1, Amount::from_micro_ccd(10));
+        host.state_mut().deposits.insert(MEMBER_2, Amount::from_micro_ccd(10));
+        let mut logger = TestLogger::init();
+        let amount = Amount::from_micro_ccd(100);
+
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(contribute(&ctx, &mut host, amount, &mut logger), Ok(()));
+
+        // The next contribution, in the second cycle, sweeps the first one.
+        let ctx = receive_ctx(MEMBER_1, 14 * DAY_MILLIS);
+        claim_eq!(contribute(&ctx, &mut host, amount, &mut logger), Ok(()));
+        claim_eq!(host.state().deposits.get(&MEMBER_1), Some(&Amount::from_micro_ccd(10)));
+        claim_eq!(host.state().deposits.get(&MEMBER_2), Some(&Amount::zero()));
+        claim_eq!(host.state().total_contributions, Amount::from_micro_ccd(210));
+
+        // A late contribution carries no extra penalty, and the missed cycle
+        // is not penalized twice.
+        let ctx = receive_ctx(MEMBER_2, 14 * DAY_MILLIS);
+        claim_eq!(contribute(&ctx, &mut host, amount, &mut logger), Ok(()));
+        claim_eq!(host.state().total_contributions, Amount::from_micro_ccd(310));
+    }
+
+    #[concordium_test]
+    fn test_state_transitions() {
+        claim_eq!(transition(TandaState::Open, TandaState::Closed), Ok(()));
+        claim_eq!(transition(TandaState::Closed, TandaState::Pending), Ok(()));
+        claim_eq!(transition(TandaState::Pending, TandaState::InProgress), Ok(()));
+        claim_eq!(transition(TandaState::InProgress, TandaState::Completed), Ok(()));
+
+        claim_eq!(
+            transition(TandaState::Completed, TandaState::Open),
+            Err(Error::InvalidState)
+        );
+        claim_eq!(
+            transition(TandaState::Closed, TandaState::InProgress),
+            Err(Error::InvalidState)
+        );
+        claim_eq!(
+            transition(TandaState::InProgress, TandaState::Pending),
+            Err(Error::InvalidState)
+        );
+    }
+
+    #[concordium_test]
+    fn test_get_remaining_cycles() {
+        let mut host = init_host(&default_parameter());
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(get_remaining_cycles(&ctx, &host), Ok(2));
+
+        host.state_mut().current_cycle = 1;
+        claim_eq!(get_remaining_cycles(&ctx, &host), Ok(1));
+
+        host.state_mut().current_cycle = 3;
+        claim_eq!(get_remaining_cycles(&ctx, &host), Ok(0));
+    }
+
+    #[concordium_test]
+    fn test_get_fee_schedule() {
+        let mut parameter = default_parameter();
+        parameter.join_fee = Amount::from_micro_ccd(5);
+        parameter.escalation_bps = 2500;
+        let host = init_host(&parameter);
+
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(
+            get_fee_schedule(&ctx, &host),
+            Ok(FeeSchedule {
+                join_fee: Amount::from_micro_ccd(5),
+                penalty_amount: Amount::from_micro_ccd(10),
+                late_join_multiplier: 3,
+                escalation_bps: 2500,
+                auto_penalize: false,
+            })
+        );
+    }
+
+    #[concordium_test]
+    fn test_catch_up_carries_slot_forward() {
+        let mut parameter = default_parameter();
+        parameter.payout_quorum_bps = 5000;
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        let mut logger = TestLogger::init();
+        for millis in [0, 14 * DAY_MILLIS] {
+            let ctx = receive_ctx(MEMBER_1, millis);
+            let result = contribute(&ctx, &mut host, Amount::from_micro_ccd(100), &mut logger);
+            claim_eq!(result, Ok(()));
+        }
+
+        // MEMBER_2 never paid and is left out when the payouts start.
+        let ctx = receive_ctx(CREATOR, 28 * DAY_MILLIS);
+        claim_eq!(start_withdrawal_phase(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(host.state().carried_over, vec![MEMBER_2]);
+        host.set_self_balance(Amount::from_micro_ccd(200));
+        let ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+        claim_eq!(withdraw(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(host.state().next_receiver, None);
+
+        // Two missed cycles plus the late penalty.
+        let ctx = receive_ctx(MEMBER_2, 30 * DAY_MILLIS);
+        claim_eq!(
+            catch_up(&ctx, &mut host, Amount::from_micro_ccd(200)),
+            Err(Error::InvalidContributionAmount)
+        );
+        claim_eq!(catch_up(&ctx, &mut host, Amount::from_micro_ccd(210)), Ok(()));
+        claim_eq!(host.state().next_receiver, Some(MEMBER_2));
+        claim_eq!(rotation_order(host.state()), vec![MEMBER_1, MEMBER_2]);
+
+        host.set_self_balance(Amount::from_micro_ccd(410));
+        let ctx = receive_ctx(MEMBER_2, 42 * DAY_MILLIS);
+        claim_eq!(withdraw(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(
+            host.state().payouts_received.get(&MEMBER_2),
+            Some(&Amount::from_micro_ccd(210))
+        );
+    }
+
+    #[concordium_test]
+    fn test_get_expected_total_pot() {
+        let mut host = init_host(&default_parameter());
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(get_expected_total_pot(&ctx, &host), Ok(Amount::from_micro_ccd(400)));
+
+        host.state_mut().contribution_amount = Amount::from_micro_ccd(u64::MAX / 2);
+        claim_eq!(get_expected_total_pot(&ctx, &host), Err(Error::InvalidState));
+    }
+
+    #[concordium_test]
+    fn test_abort_vote_delay() {
+        let mut host = abort_vote_host();
+        host.state_mut().abort_vote_delay = Duration::from_days(7);
+
+        let ctx = receive_ctx(MEMBER_1, 6 * DAY_MILLIS);
+        claim_eq!(vote_abort(&ctx, &mut host), Err(Error::NotStarted));
+        claim!(host.state().abort_votes.is_empty());
+
+        let ctx = receive_ctx(MEMBER_1, 7 * DAY_MILLIS);
+        claim_eq!(vote_abort(&ctx, &mut host), Ok(()));
+        claim_eq!(host.state().abort_vote_count(), 1);
+    }
+
+    #[concordium_test]
+    fn test_is_cycle_paid() {
+        let host = paid_out_host();
+        let is_paid = |cycle: u64| {
+            let parameter_bytes = to_bytes(&cycle);
+            let mut ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+            ctx.set_parameter(&parameter_bytes);
+            is_cycle_paid(&ctx, &host)
+        };
+
+        claim_eq!(is_paid(0), Ok(true));
+        claim_eq!(is_paid(1), Ok(false));
+        claim_eq!(is_paid(5), Ok(false));
+    }
+
+    #[concordium_test]
+    fn test_failed_join_leaves_no_trace() {
+        let mut host = init_host(&default_parameter());
+        let mut logger = TestLogger::init();
+        let untouched = host.state().clone();
+
+        let parameter_bytes = to_bytes(&JoinTandaParameter { penalty_amount: 10 });
+        let mut ctx = receive_ctx(MEMBER_1, 0);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            join_tanda(&ctx, &mut host, Amount::from_micro_ccd(9), &mut logger),
+            Err(Error::InvalidPenaltyAmount)
+        );
+        claim_eq!(host.state(), &untouched);
+
+        // A missing parameter is only noticed after the other checks, but
+        // still before anything is changed.
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(
+            join_tanda(&ctx, &mut host, Amount::from_micro_ccd(10), &mut logger),
+            Err(Error::ParseParamsError)
+        );
+        claim_eq!(host.state(), &untouched);
+        claim_eq!(host.state().deposits_total(), Amount::zero());
+    }
+
+    #[concordium_test]
+    fn test_get_max_member_obligation() {
+        let mut parameter = default_parameter();
+        parameter.payout_cycle = 3;
+        parameter.max_contributors = 3;
+        parameter.join_fee = Amount::from_micro_ccd(5);
+        parameter.escalation_bps = 5000;
+        let host = init_host(&parameter);
+
+        // Fee 5, late deposit 3 * 10, contributions 3 * 100, and penalties
+        // of 10 and 15 for the two cycles that can be late.
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(get_max_member_obligation(&ctx, &host), Ok(Amount::from_micro_ccd(360)));
+    }
+
+    #[concordium_test]
+    fn test_contribute_to_memberless_club() {
+        let mut host = init_host(&default_parameter());
+        let mut logger = TestLogger::init();
+
+        let ctx = receive_ctx(MEMBER_1, 0);
+        claim_eq!(
+            contribute(&ctx, &mut host, Amount::from_micro_ccd(1), &mut logger),
+            Err(Error::NotJoined)
+        );
+        host.state_mut().members = Some(vec![]);
+        claim_eq!(
+            contribute(&ctx, &mut host, Amount::from_micro_ccd(100), &mut logger),
+            Err(Error::NotJoined)
+        );
+    }
+
+    #[concordium_test]
+    fn test_get_next_payout_time() {
+        let mut state = State::test_default(&[MEMBER_1, MEMBER_2]);
+        state.contributors.insert(MEMBER_1);
+        state.contributors.insert(MEMBER_2);
+        state.total_contributions = Amount::from_micro_ccd(200);
+        let mut host = TestHost::new(state, TestStateBuilder::new());
+        host.set_self_balance(Amount::from_micro_ccd(200));
+        let mut logger = TestLogger::init();
+
+        let ctx = receive_ctx(CREATOR, 28 * DAY_MILLIS);
+        claim_eq!(get_next_payout_time(&ctx, &host), Err(Error::InvalidState));
+        claim_eq!(start_withdrawal_phase(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(
+            get_next_payout_time(&ctx, &host),
+            Ok(Timestamp::from_timestamp_millis(28 * DAY_MILLIS))
+        );
+        claim_eq!(get_next_payout_time(&ctx, &host), Ok(host.state().next_withdrawal_time));
+
+        let ctx = receive_ctx(MEMBER_1, 30 * DAY_MILLIS);
+        claim_eq!(withdraw(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(
+            get_next_payout_time(&ctx, &host),
+            Ok(Timestamp::from_timestamp_millis(44 * DAY_MILLIS))
+        );
+        claim_eq!(get_next_payout_time(&ctx, &host), Ok(host.state().next_withdrawal_time));
+    }
+
+    #[concordium_test]
+    fn test_get_member_return() {
+        let state = State::test_default(&[MEMBER_1, MEMBER_2]);
+        let mut host = TestHost::new(state, TestStateBuilder::new());
+        let mut logger = TestLogger::init();
+
+        for millis in [0, 14 * DAY_MILLIS] {
+            for member in [MEMBER_1, MEMBER_2] {
+                let ctx = receive_ctx(member, millis);
+                let result = contribute(&ctx, &mut host, Amount::from_micro_ccd(100), &mut logger);
+                claim_eq!(result, Ok(()));
+            }
+        }
+        host.set_self_balance(Amount::from_micro_ccd(400));
+
+        let ctx = receive_ctx(CREATOR, 28 * DAY_MILLIS);
+        claim_eq!(start_withdrawal_phase(&ctx, &mut host, &mut logger), Ok(()));
+        let ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+        claim_eq!(withdraw(&ctx, &mut host, &mut logger), Ok(()));
+        let ctx = receive_ctx(MEMBER_2, 42 * DAY_MILLIS);
+        claim_eq!(withdraw(&ctx, &mut host, &mut logger), Ok(()));
+        let ctx = receive_ctx(MEMBER_1, 56 * DAY_MILLIS);
+        claim_eq!(finalize(&ctx, &mut host), Ok(()));
+
+        let return_of = |member: AccountAddress| {
+            let parameter_bytes = to_bytes(&member);
+            let mut ctx = receive_ctx(CREATOR, 56 * DAY_MILLIS);
+            ctx.set_parameter(&parameter_bytes);
+            get_member_return(&ctx, &host)
+        };
+        // Both receivers got back exactly what they paid in.
+        claim_eq!(return_of(MEMBER_1), Ok(0));
+        claim_eq!(return_of(MEMBER_2), Ok(0));
+        claim_eq!(return_of(BACKUP), Err(Error::NotJoined));
+    }
+
+    /// Finalizes a club of two members holding 100 micro CCD while the
+    /// account of the first member is missing.
+    fn failed_payout_host() -> TestHost<State> {
+        let mut host = TestHost::new(
+            State::test_default(&[MEMBER_1, MEMBER_2]),
+            TestStateBuilder::new(),
+        );
+        host.state_mut().current_cycle = 2;
+        host.set_self_balance(Amount::from_micro_ccd(100));
+        host.make_account_missing(MEMBER_1);
+
+        let ctx = receive_ctx(MEMBER_2, 100 * DAY_MILLIS);
+        claim_eq!(finalize(&ctx, &mut host), Ok(()));
+        claim_eq!(host.state().failed_payouts.get(&MEMBER_1), Some(&Amount::from_micro_ccd(50)));
+        claim_eq!(host.self_balance(), Amount::from_micro_ccd(50));
+        host
+    }
+
+    /// A context for retrying the failed payout in `parameter_bytes`.
+    fn retry_ctx<'a>(parameter_bytes: &'a [u8]) -> TestReceiveContext<'a> {
+        let mut ctx = receive_ctx(MEMBER_2, 100 * DAY_MILLIS);
+        ctx.set_parameter(parameter_bytes);
+        ctx
+    }
+
+    #[concordium_test]
+    fn test_retry_failed_payout() {
+        let mut host = failed_payout_host();
+        let parameter_bytes = to_bytes(&MEMBER_1);
+        claim_eq!(retry_payout(&retry_ctx(&parameter_bytes), &mut host), Ok(false));
+        claim_eq!(host.state().payout_retries.get(&MEMBER_1), Some(&1));
+
+        // The account exists again.
+        let mut host = TestHost::new(host.state().clone(), TestStateBuilder::new());
+        host.set_self_balance(Amount::from_micro_ccd(50));
+        claim_eq!(retry_payout(&retry_ctx(&parameter_bytes), &mut host), Ok(true));
+        claim!(host.transfer_occurred(&MEMBER_1, Amount::from_micro_ccd(50)));
+        claim!(host.state().failed_payouts.is_empty());
+        claim!(host.state().payout_retries.is_empty());
+        claim_eq!(
+            retry_payout(&retry_ctx(&parameter_bytes), &mut host),
+            Err(Error::NothingToClaim)
+        );
+    }
+
+    #[concordium_test]
+    fn test_retry_payout_to_null_account() {
+        // A corrupted roster lists the null account as a member.
+        let mut state = State::test_default(&[CREATOR, NULL_ACCOUNT]);
+        state.failed_payouts.insert(CREATOR, Amount::from_micro_ccd(50));
+        state.failed_payouts.insert(NULL_ACCOUNT, Amount::from_micro_ccd(50));
+        let mut host = TestHost::new(state, TestStateBuilder::new());
+        host.set_self_balance(Amount::from_micro_ccd(100));
+
+        let parameter_bytes = to_bytes(&NULL_ACCOUNT);
+        claim_eq!(
+            retry_payout(&retry_ctx(&parameter_bytes), &mut host),
+            Err(Error::InvalidAddress)
+        );
+        claim!(host.get_transfers().is_empty());
+
+        let parameter_bytes = to_bytes(&CREATOR);
+        claim_eq!(retry_payout(&retry_ctx(&parameter_bytes), &mut host), Ok(true));
+        claim!(host.transfer_occurred(&CREATOR, Amount::from_micro_ccd(50)));
+        claim_eq!(host.state().failed_payouts.len(), 1);
+    }
+
+    #[concordium_test]
+    fn test_retry_payout_exhausted() {
+        let mut host = failed_payout_host();
+        let total_contributions = host.state().total_contributions;
+        let parameter_bytes = to_bytes(&MEMBER_1);
+        for _ in 0..MAX_PAYOUT_RETRIES {
+            claim_eq!(retry_payout(&retry_ctx(&parameter_bytes), &mut host), Ok(false));
+        }
+        claim!(host.state().failed_payouts.is_empty());
+        claim!(host.state().payout_retries.is_empty());
+        claim_eq!(
+            host.state().total_contributions,
+            total_contributions + Amount::from_micro_ccd(50)
+        );
+        claim_eq!(
+            retry_payout(&retry_ctx(&parameter_bytes), &mut host),
+            Err(Error::NothingToClaim)
+        );
+    }
+
+    #[concordium_test]
+    fn test_get_my_claimable() {
+        let mut host = paid_out_host();
+
+        let ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+        claim_eq!(get_my_claimable(&ctx, &host), Ok(Amount::from_micro_ccd(100)));
+        let ctx = receive_ctx(MEMBER_2, 28 * DAY_MILLIS);
+        claim_eq!(get_my_claimable(&ctx, &host), Ok(Amount::zero()));
+
+        let ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+        claim_eq!(claim_payout(&ctx, &mut host), Ok(()));
+        claim_eq!(get_my_claimable(&ctx, &host), Ok(Amount::zero()));
+    }
+
+    #[concordium_test]
+    fn test_min_join_gap() {
+        let member_3 = AccountAddress([3u8; 32]);
+        let mut parameter = default_parameter();
+        parameter.max_contributors = 3;
+        parameter.min_join_gap = Duration::from_hours(1);
+        let mut host = init_host(&parameter);
+        let mut logger = TestLogger::init();
+        let parameter_bytes = to_bytes(&JoinTandaParameter { penalty_amount: 10 });
+        let hour_millis = 60 * 60 * 1000;
+
+        let mut ctx = receive_ctx(MEMBER_1, 0);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(join_tanda(&ctx, &mut host, Amount::from_micro_ccd(10), &mut logger), Ok(()));
+
+        let mut ctx = receive_ctx(MEMBER_2, hour_millis - 1);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            join_tanda(&ctx, &mut host, Amount::from_micro_ccd(10), &mut logger),
+            Err(Error::JoinTooSoon)
+        );
+
+        let mut ctx = receive_ctx(MEMBER_2, hour_millis);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(join_tanda(&ctx, &mut host, Amount::from_micro_ccd(10), &mut logger), Ok(()));
+
+        let mut ctx = receive_ctx(member_3, 3 * hour_millis);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(join_tanda(&ctx, &mut host, Amount::from_micro_ccd(10), &mut logger), Ok(()));
+        claim_eq!(
+            host.state().last_join_time,
+            Some(Timestamp::from_timestamp_millis(3 * hour_millis))
+        );
+    }
+
+    #[concordium_test]
+    fn test_get_non_default_config() {
+        let mut parameter = default_parameter();
+        parameter.late_join_multiplier = 1;
+        let host = init_host(&parameter);
+        let ctx = receive_ctx(CREATOR, 0);
+        claim_eq!(get_non_default_config(&ctx, &host), Ok(Vec::new()));
+
+        parameter.allow_prepay = true;
+        parameter.join_fee = Amount::from_micro_ccd(5);
+        parameter.payout_quorum_bps = 8000;
+        let host = init_host(&parameter);
+        claim_eq!(
+            get_non_default_config(&ctx, &host),
+            Ok(vec![
+                ConfigField::AllowPrepay(true),
+                ConfigField::JoinFee(Amount::from_micro_ccd(5)),
+                ConfigField::PayoutQuorumBps(8000),
+            ])
+        );
+    }
+
+    #[concordium_test]
+    fn test_join_parameter_matches_deposit() {
+        let mut host = init_host(&default_parameter());
+        let mut logger = TestLogger::init();
+
+        let parameter_bytes = to_bytes(&JoinTandaParameter { penalty_amount: 11 });
+        let mut ctx = receive_ctx(MEMBER_1, 0);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            join_tanda(&ctx, &mut host, Amount::from_micro_ccd(10), &mut logger),
+            Err(Error::InvalidPenaltyAmount)
+        );
+
+        let parameter_bytes = to_bytes(&JoinTandaParameter { penalty_amount: 10 });
+        let mut ctx = receive_ctx(MEMBER_1, 0);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(join_tanda(&ctx, &mut host, Amount::from_micro_ccd(10), &mut logger), Ok(()));
+        // Joining leaves the configured penalty as it is.
+        claim_eq!(host.state().penalty_amount, Amount::from_micro_ccd(10));
+
+        // A late joiner agrees to the multiplied deposit.
+        let mut ctx = receive_ctx(MEMBER_2, 14 * DAY_MILLIS);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            join_tanda(&ctx, &mut host, Amount::from_micro_ccd(30), &mut logger),
+            Err(Error::InvalidPenaltyAmount)
+        );
+        let parameter_bytes = to_bytes(&JoinTandaParameter { penalty_amount: 30 });
+        let mut ctx = receive_ctx(MEMBER_2, 14 * DAY_MILLIS);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(join_tanda(&ctx, &mut host, Amount::from_micro_ccd(30), &mut logger), Ok(()));
+    }
+
+    #[concordium_test]
+    fn test_get_provenance() {
+        let parameter_bytes = to_bytes(&default_parameter());
+        let mut ctx = TestInitContext::empty();
+        ctx.set_init_origin(MEMBER_2);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(DAY_MILLIS));
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let state = tanda_init(&ctx, &mut state_builder).expect_report("Initialization failed");
+        let host = TestHost::new(state, state_builder);
+
+        let ctx = receive_ctx(MEMBER_1, 10 * DAY_MILLIS);
+        claim_eq!(
+            get_provenance(&ctx, &host),
+            Ok(Provenance {
+                init_origin: MEMBER_2,
+                initialized_at: Timestamp::from_timestamp_millis(DAY_MILLIS),
+            })
+        );
+    }
+
+    #[concordium_test]
+    fn test_early_bird_penalty() {
+        let member_3 = AccountAddress([3u8; 32]);
+        let mut parameter = default_parameter();
+        parameter.max_contributors = 3;
+        parameter.early_bird_count = 2;
+        parameter.early_bird_penalty = Amount::from_micro_ccd(4);
+        let mut host = init_host(&parameter);
+
+        let join = |host: &mut TestHost<State>, member: AccountAddress, deposit: u64| {
+            let parameter_bytes = to_bytes(&JoinTandaParameter { penalty_amount: deposit });
+            let mut ctx = receive_ctx(member, 0);
+            ctx.set_parameter(&parameter_bytes);
+            join_tanda(&ctx, host, Amount::from_micro_ccd(deposit), &mut TestLogger::init())
+        };
+        claim_eq!(join(&mut host, MEMBER_1, 4), Ok(()));
+        claim_eq!(join(&mut host, MEMBER_2, 4), Ok(()));
+        claim_eq!(join(&mut host, member_3, 4), Err(Error::InvalidPenaltyAmount));
+        claim_eq!(join(&mut host, member_3, 10), Ok(()));
+        claim_eq!(host.state().deposits.get(&MEMBER_2), Some(&Amount::from_micro_ccd(4)));
+        claim_eq!(host.state().deposits.get(&member_3), Some(&Amount::from_micro_ccd(10)));
+
+        parameter.early_bird_penalty = Amount::from_micro_ccd(11);
+        claim_eq!(
+            try_init(&parameter).map(|_| ()),
+            Err(Reject::from(Error::InvalidPenaltyAmount))
+        );
+    }
+
+    #[concordium_test]
+    fn test_get_cycle_contribution_counts() {
+        let member_3 = AccountAddress([3u8; 32]);
+        let mut parameter = default_parameter();
+        parameter.payout_cycle = 3;
+        parameter.max_contributors = 3;
+        let mut host = init_host(&parameter);
+        host.state_mut().contributed_cycles.insert(MEMBER_1, BTreeSet::from([0, 1, 2]));
+        host.state_mut().contributed_cycles.insert(MEMBER_2, BTreeSet::from([0, 2]));
+        host.state_mut().contributed_cycles.insert(member_3, BTreeSet::from([0]));
+
+        let ctx = receive_ctx(MEMBER_1, 15 * DAY_MILLIS);
+        claim_eq!(get_cycle_contribution_counts(&ctx, &host), Ok(vec![(0, 3), (1, 1)]));
+        let ctx = receive_ctx(MEMBER_1, 60 * DAY_MILLIS);
+        claim_eq!(
+            get_cycle_contribution_counts(&ctx, &host),
+            Ok(vec![(0, 3), (1, 1), (2, 2)])
+        );
+    }
+
+    #[concordium_test]
+    fn test_collateral_collected_on_join() {
+        let mut parameter = default_parameter();
+        parameter.collateral_amount = Amount::from_micro_ccd(50);
+        let mut host = init_host(&parameter);
+        let mut logger = TestLogger::init();
+
+        let parameter_bytes = to_bytes(&JoinTandaParameter { penalty_amount: 10 });
+        let mut ctx = receive_ctx(MEMBER_1, 0);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            join_tanda(&ctx, &mut host, Amount::from_micro_ccd(10), &mut logger),
+            Err(Error::InvalidPenaltyAmount)
+        );
+        claim_eq!(join_tanda(&ctx, &mut host, Amount::from_micro_ccd(60), &mut logger), Ok(()));
+        claim_eq!(host.state().deposits.get(&MEMBER_1), Some(&Amount::from_micro_ccd(10)));
+        claim_eq!(host.state().collaterals.get(&MEMBER_1), Some(&Amount::from_micro_ccd(50)));
+        claim_eq!(host.state().deposits_total(), Amount::from_micro_ccd(60));
+
+        // Leaving forfeits the collateral along with the deposit.
+        host.set_self_balance(Amount::from_micro_ccd(60));
+        claim_eq!(forfeit(&ctx, &mut host), Ok(()));
+        claim!(host.state().collaterals.is_empty());
+        claim_eq!(host.state().total_contributions, Amount::from_micro_ccd(60));
+    }
+
+    #[concordium_test]
+    fn test_collateral_released_on_finalize() {
+        let mut host = TestHost::new(
+            State::test_default(&[MEMBER_1, MEMBER_2]),
+            TestStateBuilder::new(),
+        );
+        host.state_mut().current_cycle = 2;
+        host.state_mut().collaterals.insert(MEMBER_1, Amount::from_micro_ccd(50));
+        host.state_mut().collaterals.insert(MEMBER_2, Amount::from_micro_ccd(50));
+        host.state_mut().defaults.insert(MEMBER_2, 1);
+        host.set_self_balance(Amount::from_micro_ccd(100));
+
+        let ctx = receive_ctx(MEMBER_1, 100 * DAY_MILLIS);
+        claim_eq!(finalize(&ctx, &mut host), Ok(()));
+        claim!(host.state().collaterals.is_empty());
+        // The honest member gets their collateral back to claim.
+        claim_eq!(
+            host.state().withdrawable_balances.get(&MEMBER_1),
+            Some(&Amount::from_micro_ccd(50))
+        );
+        claim_eq!(host.state().withdrawable_balances.get(&MEMBER_2), None);
+        // The defaulter's collateral is shared as part of the remainder.
+        claim!(host.transfer_occurred(&MEMBER_1, Amount::from_micro_ccd(25)));
+        claim!(host.transfer_occurred(&MEMBER_2, Amount::from_micro_ccd(25)));
+    }
+
+    #[concordium_test]
+    fn test_are_co_members() {
+        let host = TestHost::new(State::test_default(&[MEMBER_1, MEMBER_2]), TestStateBuilder::new());
+        let stranger = AccountAddress([3u8; 32]);
+
+        let co_members = |pair: (AccountAddress, AccountAddress)| {
+            let parameter_bytes = to_bytes(&pair);
+            let mut ctx = receive_ctx(CREATOR, 0);
+            ctx.set_parameter(&parameter_bytes);
+            are_co_members(&ctx, &host)
+        };
+        claim_eq!(co_members((MEMBER_1, MEMBER_2)), Ok(true));
+        claim_eq!(co_members((MEMBER_1, stranger)), Ok(false));
+        claim_eq!(co_members((stranger, BACKUP)), Ok(false));
+    }
+
+    #[concordium_test]
+    fn test_get_last_joined() {
+        let mut host = init_host(&default_parameter());
+        let mut logger = TestLogger::init();
+        let ctx = receive_ctx(CREATOR, 0);
+        claim_eq!(get_last_joined(&ctx, &host), Ok(None));
+
+        let parameter_bytes = to_bytes(&JoinTandaParameter { penalty_amount: 10 });
+        for (member, millis) in [(MEMBER_1, DAY_MILLIS), (MEMBER_2, 2 * DAY_MILLIS)] {
+            let mut ctx = receive_ctx(member, millis);
+            ctx.set_parameter(&parameter_bytes);
+            claim_eq!(
+                join_tanda(&ctx, &mut host, Amount::from_micro_ccd(10), &mut logger),
+                Ok(())
+            );
+            claim_eq!(
+                get_last_joined(&ctx, &host),
+                Ok(Some((member, Timestamp::from_timestamp_millis(millis))))
+            );
+        }
+    }
+
+    #[concordium_test]
+    fn test_get_member_payout_cycles() {
+        let mut host = paid_out_host();
+        let payout_cycles = |host: &TestHost<State>, member: AccountAddress| {
+            let parameter_bytes = to_bytes(&member);
+            let mut ctx = receive_ctx(CREATOR, 30 * DAY_MILLIS);
+            ctx.set_parameter(&parameter_bytes);
+            get_member_payout_cycles(&ctx, host)
+        };
+        claim_eq!(payout_cycles(&host, MEMBER_1), Ok(vec![0]));
+        claim_eq!(payout_cycles(&host, MEMBER_2), Ok(Vec::new()));
+
+        // A member paid in several cycles has all of them listed.
+        host.state_mut().completed_cycles.push((1, vec![MEMBER_2]));
+        host.state_mut().completed_cycles.push((2, vec![MEMBER_1]));
+        claim_eq!(payout_cycles(&host, MEMBER_1), Ok(vec![0, 2]));
+        claim_eq!(payout_cycles(&host, MEMBER_2), Ok(vec![1]));
+    }
+
+    #[concordium_test]
+    fn test_is_my_withdrawal_due() {
+        let mut state = State::test_default(&[MEMBER_1, MEMBER_2]);
+        state.contributors.insert(MEMBER_1);
+        state.contributors.insert(MEMBER_2);
+        state.total_contributions = Amount::from_micro_ccd(200);
+        let mut host = TestHost::new(state, TestStateBuilder::new());
+        host.set_self_balance(Amount::from_micro_ccd(200));
+        let mut logger = TestLogger::init();
+
+        let ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+        claim_eq!(is_my_withdrawal_due(&ctx, &host), Ok(false));
+        let ctx = receive_ctx(CREATOR, 28 * DAY_MILLIS);
+        claim_eq!(start_withdrawal_phase(&ctx, &mut host, &mut logger), Ok(()));
+
+        let ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+        claim_eq!(is_my_withdrawal_due(&ctx, &host), Ok(true));
+        claim_eq!(withdraw(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(is_my_withdrawal_due(&ctx, &host), Ok(false));
+
+        // The next receiver is due once the interval has passed.
+        let ctx = receive_ctx(MEMBER_2, 41 * DAY_MILLIS);
+        claim_eq!(is_my_withdrawal_due(&ctx, &host), Ok(false));
+        let ctx = receive_ctx(MEMBER_2, 42 * DAY_MILLIS);
+        claim_eq!(is_my_withdrawal_due(&ctx, &host), Ok(true));
+        let ctx = receive_ctx(BACKUP, 42 * DAY_MILLIS);
+        claim_eq!(is_my_withdrawal_due(&ctx, &host), Ok(false));
+    }
+
+    #[concordium_test]
+    fn test_start_withdrawal_phase_waits_for_contribution_phase() {
+        let mut host = contributed_host();
+        let mut logger = TestLogger::init();
+
+        // Every member has contributed, but the last contribution cycle is
+        // still running.
+        let ctx = receive_ctx(CREATOR, 28 * DAY_MILLIS - 1);
+        claim!(host.state().accepts_contributions(ctx.metadata().slot_time()));
+        claim_eq!(
+            start_withdrawal_phase(&ctx, &mut host, &mut logger),
+            Err(Error::WithdrawalIntervalNotReached)
+        );
+        claim!(!host.state().withdrawal_phase_started);
+
+        let ctx = receive_ctx(CREATOR, 28 * DAY_MILLIS);
+        claim!(!host.state().accepts_contributions(ctx.metadata().slot_time()));
+        claim_eq!(start_withdrawal_phase(&ctx, &mut host, &mut logger), Ok(()));
+    }
+
+    #[concordium_test]
+    fn test_min_pot_for_payout() {
+        let mut host = contributed_host();
+        let mut logger = TestLogger::init();
+        let ctx = receive_ctx(CREATOR, 28 * DAY_MILLIS);
+        claim_eq!(start_withdrawal_phase(&ctx, &mut host, &mut logger), Ok(()));
+
+        let ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+        host.state_mut().min_pot_for_payout = Amount::from_micro_ccd(101);
+        claim_eq!(withdraw(&ctx, &mut host, &mut logger), Err(Error::InsufficientBalance));
+        claim!(host.state().withdrawable_balances.is_empty());
+
+        host.state_mut().min_pot_for_payout = Amount::from_micro_ccd(100);
+        claim_eq!(withdraw(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(
+            host.state().withdrawable_balances.get(&MEMBER_1),
+            Some(&Amount::from_micro_ccd(100))
+        );
+    }
+
+    #[concordium_test]
+    fn test_get_activity() {
+        let mut host = init_host(&default_parameter());
+        let mut logger = TestLogger::init();
+        let ctx = receive_ctx(CREATOR, 0);
+        claim_eq!(get_activity(&ctx, &host), Ok(Vec::new()));
+
+        let parameter_bytes = to_bytes(&JoinTandaParameter { penalty_amount: 10 });
+        let mut ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(join_tanda(&ctx, &mut host, Amount::from_micro_ccd(10), &mut logger), Ok(()));
+        let ctx = receive_ctx(MEMBER_1, 2 * DAY_MILLIS);
+        claim_eq!(
+            contribute(&ctx, &mut host, Amount::from_micro_ccd(100), &mut logger),
+            Ok(())
+        );
+        claim_eq!(
+            get_activity(&ctx, &host),
+            Ok(vec![
+                ("contribute".into(), Timestamp::from_timestamp_millis(2 * DAY_MILLIS)),
+                ("joinTanda".into(), Timestamp::from_timestamp_millis(DAY_MILLIS)),
+            ])
+        );
+
+        // Calling an entrypoint again moves its timestamp.
+        let parameter_bytes = to_bytes(&String::from("Savings"));
+        let mut ctx = receive_ctx(CREATOR, 3 * DAY_MILLIS);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(rename(&ctx, &mut host, &mut logger), Ok(()));
+        let mut ctx = receive_ctx(CREATOR, 4 * DAY_MILLIS);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(rename(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(
+            host.state().activity.get("rename"),
+            Some(&Timestamp::from_timestamp_millis(4 * DAY_MILLIS))
+        );
+        claim_eq!(host.state().activity.len(), 3);
+    }
+
+    #[concordium_test]
+    fn test_soft_close() {
+        let mut parameter = default_parameter();
+        parameter.max_contributors = 4;
+        parameter.soft_close_bps = Some(7500);
+        let mut host = init_host(&parameter);
+        let mut logger = TestLogger::init();
+
+        let parameter_bytes = to_bytes(&JoinTandaParameter { penalty_amount: 10 });
+        for i in 1..=3u8 {
+            // Joining is still open below three of the four slots.
+            claim_eq!(host.state().tanda_state, TandaState::Open);
+            let mut ctx = receive_ctx(AccountAddress([i; 32]), 0);
+            ctx.set_parameter(&parameter_bytes);
+            claim_eq!(
+                join_tanda(&ctx, &mut host, Amount::from_micro_ccd(10), &mut logger),
+                Ok(())
+            );
+        }
+        claim_eq!(host.state().tanda_state, TandaState::Closed);
+
+        let mut ctx = receive_ctx(AccountAddress([4u8; 32]), 0);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            join_tanda(&ctx, &mut host, Amount::from_micro_ccd(10), &mut logger),
+            Err(Error::TandaClosed)
+        );
+
+        parameter.soft_close_bps = Some(0);
+        claim_eq!(try_init(&parameter).map(|_| ()), Err(Reject::from(Error::InvalidSoftClose)));
+    }
+
+    #[concordium_test]
+    fn test_get_total_refundable() {
+        let mut host = abort_vote_host();
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        claim_eq!(get_total_refundable(&ctx, &host), Ok(Amount::from_micro_ccd(330)));
+
+        host.set_self_balance(Amount::from_micro_ccd(300));
+        claim_eq!(get_total_refundable(&ctx, &host), Ok(Amount::from_micro_ccd(300)));
+        host.set_self_balance(Amount::from_micro_ccd(330));
+
+        // The total matches what aborting actually credits.
+        let expected = get_total_refundable(&ctx, &host).unwrap_abort();
+        claim_eq!(vote_abort(&ctx, &mut host), Ok(()));
+        let ctx = receive_ctx(MEMBER_2, DAY_MILLIS);
+        claim_eq!(vote_abort(&ctx, &mut host), Ok(()));
+        let credited: u64 = host
+            .state()
+            .withdrawable_balances
+            .values()
+            .map(|refund| refund.micro_ccd)
+            .sum();
+        claim_eq!(Amount::from_micro_ccd(credited), expected);
+    }
+
+    #[concordium_test]
+    fn test_repair_members() {
+        let mut host = init_host(&default_parameter());
+        for member in [MEMBER_1, MEMBER_2] {
+            host.state_mut().contributors.insert(member);
+            host.state_mut().joined_at.insert(member, Timestamp::from_timestamp_millis(0));
+        }
+        host.state_mut().members = None;
+
+        // A roster leaving out a contributor does not repair the list.
+        let parameter_bytes = to_bytes(&vec![(MEMBER_1, 1u64)]);
+        let mut ctx = receive_ctx(CREATOR, DAY_MILLIS);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(repair_members(&ctx, &mut host), Err(Error::InvalidParameter));
+
+        let roster = vec![(MEMBER_1, 1u64), (MEMBER_2, 2)];
+        let parameter_bytes = to_bytes(&roster);
+        let mut ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(repair_members(&ctx, &mut host), Err(Error::NotAuthorized));
+
+        // Accounts that never joined cannot be added.
+        let outsider_bytes = to_bytes(&vec![(MEMBER_1, 1u64), (MEMBER_2, 2), (BACKUP, 3)]);
+        let mut ctx = receive_ctx(CREATOR, DAY_MILLIS);
+        ctx.set_parameter(&outsider_bytes);
+        claim_eq!(repair_members(&ctx, &mut host), Err(Error::NotJoined));
+
+        let mut ctx = receive_ctx(CREATOR, DAY_MILLIS);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(repair_members(&ctx, &mut host), Ok(()));
+        claim_eq!(host.state().members, Some(roster));
+
+        // Once consistent, the list cannot be replaced.
+        let parameter_bytes = to_bytes(&vec![(MEMBER_2, 1u64), (MEMBER_1, 2)]);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(repair_members(&ctx, &mut host), Err(Error::InvalidState));
+
+        // A list left out of order by a deferral is not corrupt either.
+        host.state_mut().members = Some(vec![(MEMBER_2, 2), (MEMBER_1, 3)]);
+        claim_eq!(repair_members(&ctx, &mut host), Err(Error::InvalidState));
+    }
+
+    #[concordium_test]
+    fn test_withdraw_penalty_amount_keeps_members() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        let mut logger = TestLogger::init();
+
+        let ctx = receive_ctx(MEMBER_2, DAY_MILLIS);
+        claim_eq!(
+            withdraw_penalty_amount(&ctx, &mut host, &mut logger),
+            Err(Error::NotJoined)
+        );
+        let ctx = receive_ctx(MEMBER_1, DAY_MILLIS);
+        claim_eq!(withdraw_penalty_amount(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(host.state().members, Some(vec![(MEMBER_1, 1)]));
+    }
+
+    #[concordium_test]
+    fn test_rotation_splits_pot_per_cycle() {
+        let member_3 = AccountAddress([3u8; 32]);
+        let mut parameter = default_parameter();
+        parameter.payout_cycle = 3;
+        parameter.max_contributors = 3;
+        let mut host = init_host(&parameter);
+        let members = vec![(MEMBER_1, 1), (MEMBER_2, 2), (member_3, 3)];
+        host.state_mut().members = Some(members.clone());
+        host.state_mut().total_contributions = Amount::from_micro_ccd(301);
+
+        // The last receiver also takes the micro CCD left by the split.
+        let expected = [100, 100, 101];
+        for (cycle, (address, _)) in members.iter().enumerate() {
+            let now = Timestamp::from_timestamp_millis((cycle as u64 + 2) * 14 * DAY_MILLIS);
+            claim_eq!(pay_out(host.state_mut(), *address, cycle as u64, now), Ok(()));
+        }
+        for (index, (address, _)) in members.iter().enumerate() {
+            claim_eq!(
+                host.state().withdrawable_balances.get(address),
+                Some(&Amount::from_micro_ccd(expected[index]))
+            );
+        }
+        claim_eq!(host.state().distributable_pot(), Amount::zero());
+    }
+
+    #[concordium_test]
+    fn test_abort_refunds_waitlist() {
+        let mut host = abort_vote_host();
+        host.state_mut().waitlist.push((BACKUP, Amount::from_micro_ccd(10)));
+        host.set_self_balance(Amount::from_micro_ccd(340));
+
+        for member in [MEMBER_1, MEMBER_2] {
+            let ctx = receive_ctx(member, DAY_MILLIS);
+            claim_eq!(vote_abort(&ctx, &mut host), Ok(()));
+        }
+        claim!(host.state().aborted);
+        claim!(host.state().waitlist.is_empty());
+        claim_eq!(
+            host.state().withdrawable_balances.get(&BACKUP),
+            Some(&Amount::from_micro_ccd(10))
+        );
+        claim_eq!(
+            host.state().withdrawable_balances.get(&MEMBER_1),
+            Some(&Amount::from_micro_ccd(110))
+        );
+    }
+
+    #[concordium_test]
+    fn test_start_withdrawal_phase_after_closing_early() {
+        let member_3 = AccountAddress([3u8; 32]);
+        let mut parameter = default_parameter();
+        parameter.max_contributors = 4;
+        let mut host = init_host(&parameter);
+        let members = vec![(MEMBER_1, 1), (MEMBER_2, 2), (member_3, 3)];
+        host.state_mut().members = Some(members);
+        let mut logger = TestLogger::init();
+
+        let ctx = receive_ctx(CREATOR, DAY_MILLIS);
+        claim_eq!(close_joining(&ctx, &mut host), Ok(()));
+        for member in [MEMBER_1, MEMBER_2, member_3] {
+            host.state_mut().contributors.insert(member);
+        }
+
+        // Everybody who joined paid, so the full quorum is met.
+        let ctx = receive_ctx(CREATOR, 28 * DAY_MILLIS);
+        claim_eq!(start_withdrawal_phase(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(host.state().next_receiver, Some(MEMBER_1));
+    }
+
+    #[concordium_test]
+    fn test_finalize_without_receivers_left() {
+        let mut parameter = default_parameter();
+        parameter.payout_quorum_bps = 5000;
+        let mut host = init_host(&parameter);
+        host.state_mut().members = Some(vec![(MEMBER_1, 1), (MEMBER_2, 2)]);
+        let mut logger = TestLogger::init();
+        for millis in [0, 14 * DAY_MILLIS] {
+            let ctx = receive_ctx(MEMBER_1, millis);
+            let result = contribute(&ctx, &mut host, Amount::from_micro_ccd(100), &mut logger);
+            claim_eq!(result, Ok(()));
+        }
+        host.set_self_balance(Amount::from_micro_ccd(200));
+
+        // MEMBER_2 never paid, so only one of the two cycles has a receiver.
+        let ctx = receive_ctx(CREATOR, 28 * DAY_MILLIS);
+        claim_eq!(start_withdrawal_phase(&ctx, &mut host, &mut logger), Ok(()));
+        let ctx = receive_ctx(MEMBER_1, 28 * DAY_MILLIS);
+        claim_eq!(withdraw(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(host.state().next_receiver, None);
+
+        let ctx = receive_ctx(MEMBER_1, 41 * DAY_MILLIS);
+        claim_eq!(finalize(&ctx, &mut host), Err(Error::NotStarted));
+        let ctx = receive_ctx(MEMBER_1, 42 * DAY_MILLIS);
+        claim_eq!(finalize(&ctx, &mut host), Ok(()));
+        claim_eq!(host.state().tanda_state, TandaState::Completed);
+        claim_eq!(host.state().current_cycle, 2);
+    }
+
+    #[concordium_test]
+    fn test_catch_up_requires_membership() {
+        let mut host = init_host(&default_parameter());
+        host.state_mut().members = Some(vec![(MEMBER_1, 1)]);
+        // MEMBER_2 was carried over and then forfeited.
+        host.state_mut().carried_over.push(MEMBER_2);
+        host.state_mut().tanda_state = TandaState::Pending;
+
+        let ctx = receive_ctx(MEMBER_2, 30 * DAY_MILLIS);
+        claim_eq!(
+            catch_up(&ctx, &mut host, Amount::from_micro_ccd(210)),
+            Err(Error::NotJoined)
+        );
+        claim_eq!(host.state().total_contributions, Amount::zero());
+    }
+
+    #[concordium_test]
+    fn test_pay_out_next_time_overflow() {
+        let mut host = contributed_host();
+        let untouched = host.state().clone();
+        let now = Timestamp::from_timestamp_millis(u64::MAX - 1);
+        claim_eq!(pay_out(host.state_mut(), MEMBER_1, 0, now), Err(Error::InvalidState));
+        claim_eq!(*host.state(), untouched);
+    }
+}